@@ -1,4 +1,15 @@
-use bevy::{log::LogPlugin, prelude::*};
+use std::path::Path;
+
+use bevy::{
+    hierarchy::HierarchyPlugin, input::InputPlugin, log::LogPlugin, prelude::*,
+    state::app::StatesPlugin, transform::TransformPlugin,
+};
+use bevy_spine::{Atlas, SkeletonBinary, SkeletonData, SkeletonJson};
+use mod_level::LevelType;
+use scene_game::{
+    headless::{HeadlessResult, SceneGameHeadlessPlugin},
+    replay::load_replay,
+};
 
 pub fn main() {
     App::new()
@@ -35,10 +46,117 @@ pub fn main() {
         .add_plugins(mod_item::ModItemPlugin)
         .add_plugins(mod_level::ModLevelPlugin)
         .add_plugins(mod_userdata::ModUserdataPlugin)
+        .add_plugins(mod_net::ModNetPlugin)
         .add_plugins(scene_base::SceneBasePlugin)
         .add_plugins(scene_splash::SceneSplashPlugin)
         .add_plugins(scene_title::SceneTitlePlugin)
         .add_plugins(scene_game::SceneGamePlugin)
         .add_plugins(scene_reward::SceneRewardPlugin)
+        .add_plugins(scene_lobby::SceneLobbyPlugin)
+        .add_plugins(scene_almanac::SceneAlmanacPlugin)
+        .add_plugins(scene_shop::SceneShopPlugin)
+        .add_plugins(scene_level_select::SceneLevelSelectPlugin)
         .run();
 }
+
+// 无窗口模式运行一局，参数为随机数种子与冒险模式关卡
+// 参数：<seed> <level> 或 --replay <path>
+pub fn headless_main() {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().is_some_and(|arg| arg == "--replay") {
+        let Some(path) = args.nth(1) else {
+            eprintln!("missing replay path");
+            return;
+        };
+        let Some(replay) = load_replay(Path::new(&path)) else {
+            eprintln!("failed to load replay {path}");
+            return;
+        };
+
+        let result = run_headless(SceneGameHeadlessPlugin {
+            replay: Some(replay),
+            ..Default::default()
+        });
+        println!(
+            "replay: {path}, outcome: {:?}, frames: {}",
+            result.outcome, result.frames
+        );
+        return;
+    }
+
+    let seed = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(0);
+    let level = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(1);
+
+    let result = run_headless(SceneGameHeadlessPlugin {
+        seed,
+        level: LevelType::Adventure { level },
+        ..Default::default()
+    });
+    println!(
+        "seed: {seed}, level: {level}, outcome: {:?}, frames: {}",
+        result.outcome, result.frames
+    );
+}
+
+// 不创建窗口、音频设备与渲染管线，尽可能快地运行到对局结束
+pub fn run_headless(headless: SceneGameHeadlessPlugin) -> HeadlessResult {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        InputPlugin,
+        StatesPlugin,
+    ))
+    // 只注册资源类型，不注册加载器，资源加载失败不影响对局逻辑
+    .init_asset::<Image>()
+    .init_asset::<Font>()
+    .init_asset::<Mesh>()
+    .init_asset::<ColorMaterial>()
+    .init_asset::<AudioSource>()
+    .init_asset::<Atlas>()
+    .init_asset::<SkeletonJson>()
+    .init_asset::<SkeletonBinary>()
+    .init_asset::<SkeletonData>()
+    .add_plugins(fw_anim::FwAnimPlugin)
+    .add_plugins(mod_plant::ModPlantPlugin)
+    .add_plugins(mod_zombie::ModZombiePlugin)
+    .add_plugins(mod_item::ModItemPlugin)
+    .add_plugins(mod_level::ModLevelPlugin)
+    .add_plugins(scene_base::SceneBasePlugin)
+    .add_plugins(headless)
+    .add_plugins(scene_game::SceneGamePlugin);
+
+    // 不使用 ScheduleRunnerPlugin 的循环，以便结束后读取结果
+    app.finish();
+    app.cleanup();
+    while app.should_exit().is_none() {
+        app.update();
+    }
+
+    app.world_mut()
+        .remove_resource::<HeadlessResult>()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless_is_deterministic() {
+        // 数据文件按工作区根目录的相对路径读取
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+
+        let run = || {
+            run_headless(SceneGameHeadlessPlugin {
+                seed: 42,
+                ..Default::default()
+            })
+        };
+        let first = run();
+        assert!(first.outcome.is_some());
+        assert_eq!(first, run());
+    }
+}
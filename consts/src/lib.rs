@@ -6,6 +6,7 @@ pub mod anim {
     pub const INDEX_PLANT_INSTANT: usize = 13;
     pub const INDEX_PLANT_DAMAGE_1: usize = 14;
     pub const INDEX_PLANT_DAMAGE_2: usize = 15;
+    pub const INDEX_PLANT_SLEEP: usize = 16;
     pub const INDEX_ZOMBIE_MOVE: usize = 11;
     pub const INDEX_ZOMBIE_EAT: usize = 13;
     pub const INDEX_ZOMBIE_EAT_STOP: usize = 12;
@@ -15,6 +16,8 @@ pub mod anim {
     pub const INDEX_ZOMBIE_ARMOR_1: usize = 17;
     pub const INDEX_ZOMBIE_ARMOR_2: usize = 18;
     pub const INDEX_ZOMBIE_ARMOR_3: usize = 19;
+    pub const INDEX_ZOMBIE_JUMP: usize = 20;
+    pub const INDEX_ZOMBIE_SWIM: usize = 21;
     pub const INDEX_SUN_FADE_OUT: usize = 11;
 
     pub const NAME_STANDBY: &str = "standby";
@@ -24,6 +27,7 @@ pub mod anim {
     pub const NAME_PLANT_INSTANT: &str = "instant";
     pub const NAME_PLANT_DAMAGE_1: &str = "damage_1";
     pub const NAME_PLANT_DAMAGE_2: &str = "damage_2";
+    pub const NAME_PLANT_SLEEP: &str = "sleep";
     pub const NAME_ZOMBIE_MOVE: &str = "move";
     pub const NAME_ZOMBIE_EAT: &str = "eat";
     pub const NAME_ZOMBIE_EAT_STOP: &str = "eat_stop";
@@ -33,5 +37,7 @@ pub mod anim {
     pub const NAME_ZOMBIE_ARMOR_1: &str = "armor_break_1";
     pub const NAME_ZOMBIE_ARMOR_2: &str = "armor_break_2";
     pub const NAME_ZOMBIE_ARMOR_3: &str = "armor_break_3";
+    pub const NAME_ZOMBIE_JUMP: &str = "jump";
+    pub const NAME_ZOMBIE_SWIM: &str = "swim";
     pub const NAME_SUN_FADE_OUT: &str = "fade_out";
 }
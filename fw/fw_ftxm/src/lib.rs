@@ -162,6 +162,20 @@ fn start_playback(
     }
 }
 
+// 暂停所有 XM 音乐
+pub fn pause_playback(sinks: Query<&FtxmAudioSink>) {
+    for FtxmAudioSink { sink } in &sinks {
+        sink.pause();
+    }
+}
+
+// 继续播放所有 XM 音乐
+pub fn resume_playback(sinks: Query<&FtxmAudioSink>) {
+    for FtxmAudioSink { sink } in &sinks {
+        sink.play();
+    }
+}
+
 impl From<MainMusicTable> for u8 {
     fn from(value: MainMusicTable) -> Self {
         match value {
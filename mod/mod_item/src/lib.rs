@@ -1,6 +1,7 @@
 use std::{fs::File, ops::Deref, sync::Arc};
 
 use bevy::{prelude::*, utils::HashMap};
+use mod_plant::metadata::PlantType;
 use serde::Deserialize;
 
 pub struct ModItemPlugin;
@@ -43,6 +44,11 @@ pub enum ItemType {
     Key,
     Taco,
     WateringCan,
+    // 额外的种子槽
+    SeedSlot,
+    // 升级植物
+    GatlingPea,
+    TwinSunflower,
 }
 
 #[derive(Debug, Deserialize)]
@@ -62,4 +68,12 @@ pub struct Item {
     pub name: String,
     #[serde(rename = "Description")]
     pub description: String,
+    // 商店中的价格，没有价格的物品不在商店出售
+    #[serde(rename = "Price")]
+    #[serde(default)]
+    pub price: Option<u32>,
+    // 获得物品时解锁的植物
+    #[serde(rename = "Plant")]
+    #[serde(default)]
+    pub plant: Option<PlantType>,
 }
@@ -4,7 +4,10 @@ use bevy::{prelude::*, utils::HashMap};
 use mod_item::ItemType;
 use mod_plant::metadata::PlantType;
 use mod_zombie::metadata::ZombieType;
-use serde::Deserialize;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub mod note;
 
 pub struct ModLevelPlugin;
 
@@ -17,6 +20,7 @@ pub struct CurrentLevel(pub Arc<Level>);
 impl Plugin for ModLevelPlugin {
     fn build(&self, app: &mut App) {
         let levels = load_levels();
+        let notes = note::load_notes(&levels);
         let current_level = levels
             .0
             .get(&LevelType::Adventure { level: 1 })
@@ -24,6 +28,7 @@ impl Plugin for ModLevelPlugin {
             .clone();
 
         app.insert_resource(levels)
+            .insert_resource(notes)
             .insert_resource(CurrentLevel(current_level));
     }
 }
@@ -32,6 +37,14 @@ fn load_levels() -> LevelRegistry {
     let levels: Levels =
         serde_xml_rs::from_reader(File::open("./assets/data/levels.xml").unwrap()).unwrap();
     debug!("loaded levels: {:?}", levels);
+    // 对战的格子按五路草地计算，不支持泳池
+    for level in &levels.levels {
+        assert!(
+            level.versus.is_none() || !level.background.is_pool(),
+            "versus level {:?} can not use a pool background",
+            level.id
+        );
+    }
 
     LevelRegistry(
         levels
@@ -58,9 +71,10 @@ impl Deref for CurrentLevel {
     }
 }
 
-#[derive(Debug, Deserialize, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum LevelType {
     Adventure { level: u32 },
+    Survival { level: u32 },
     PlantZombie,
 }
 
@@ -99,9 +113,27 @@ pub struct Level {
     #[serde(default)]
     pub special_rule: Option<SpecialRule>,
     #[serde(rename = "PreviewZombie")]
+    #[serde(default)]
     pub preview_zombies: Vec<Zombie>,
+    // 开局时的墓碑
+    #[serde(rename = "Graves")]
+    #[serde(default)]
+    pub graves: Graves,
     #[serde(rename = "Wave")]
+    #[serde(default)]
     pub waves: Vec<Wave>,
+    // 按点数生成僵尸，存在时不使用 Wave
+    #[serde(rename = "GeneratedWaves")]
+    #[serde(default)]
+    pub generated_waves: Option<GeneratedWaves>,
+    // 生存模式，每面旗帜为一轮
+    #[serde(rename = "Survival")]
+    #[serde(default)]
+    pub survival: Option<Survival>,
+    // 双人对战，僵尸由另一名玩家放置
+    #[serde(rename = "Versus")]
+    #[serde(default)]
+    pub versus: Option<Versus>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -169,6 +201,193 @@ pub enum Music {
 }
 
 #[derive(Debug, Deserialize)]
+pub struct Graves {
+    // 开局时随机生成的墓碑数量
+    #[serde(default)]
+    pub count: u32,
+    // 墓碑只会出现在该列及其右侧，从 0 开始
+    #[serde(rename = "column")]
+    #[serde(default = "default_grave_column")]
+    pub min_column: u8,
+}
+
+impl Default for Graves {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            min_column: default_grave_column(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Survival {
+    // 旗帜数，无尽模式下忽略
+    #[serde(default = "default_survival_flags")]
+    pub flags: u32,
+    // 无尽模式，记录到达的最高旗数
+    #[serde(default = "default_false")]
+    pub endless: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Versus {
+    // 僵尸方的初始脑子
+    #[serde(default = "default_brains")]
+    pub brains: u32,
+    // 每隔多少秒获得脑子
+    #[serde(default = "default_brain_interval")]
+    pub brain_interval: f32,
+    #[serde(default = "default_brain_amount")]
+    pub brain_amount: u32,
+    // 植物方可以种植的列数，其余列由僵尸方放置僵尸
+    #[serde(default = "default_plant_columns")]
+    pub plant_columns: u8,
+    // 僵尸方每一路目标的血量，任意目标被摧毁则植物方获胜
+    #[serde(default = "default_target_hp")]
+    pub target_hp: f32,
+    // 植物方携带的植物，存在时不进入选卡界面，联机时双方需要一致
+    #[serde(rename = "Plant")]
+    #[serde(default)]
+    pub plants: Vec<PlantType>,
+    #[serde(rename = "ZombieSeed")]
+    pub zombie_seeds: Vec<ZombieSeed>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ZombieSeed {
+    #[serde(rename = "$value")]
+    pub zombie: ZombieType,
+    // 消耗的脑子
+    pub cost: u32,
+    // 冷却时间（秒）
+    pub cooldown: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GeneratedWaves {
+    // 波数，生存模式下为每轮的波数
+    #[serde(rename = "waves")]
+    pub count: u32,
+    // 每隔多少波出现一次大波僵尸，最后一波总是大波僵尸
+    #[serde(rename = "flag")]
+    #[serde(default = "default_flag_interval")]
+    pub flag_interval: u32,
+    // 第一波的点数
+    pub budget: f32,
+    // 每波增加的点数
+    #[serde(default)]
+    pub growth: f32,
+    // 随机数种子，不填写时每局不同
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(rename = "Zombie")]
+    pub zombies: Vec<GeneratedZombie>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GeneratedZombie {
+    #[serde(rename = "$value")]
+    pub zombie: ZombieType,
+    // 消耗的点数
+    pub cost: u32,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    // 从第几波开始出现（从 0 开始）
+    #[serde(default)]
+    pub wave: u32,
+}
+
+impl LevelBackground {
+    // 黑夜场景，夜行植物不会睡着
+    pub fn is_night(&self) -> bool {
+        matches!(
+            self,
+            LevelBackground::Night | LevelBackground::SwimFog | LevelBackground::RoofNight
+        )
+    }
+
+    // 泳池场景，共六路，中间两路为水路
+    pub fn is_pool(&self) -> bool {
+        matches!(self, LevelBackground::Swim | LevelBackground::SwimFog)
+    }
+}
+
+impl Level {
+    // 关卡指定的植物，存在时直接使用这些植物
+    pub fn preset_plants(&self) -> Option<&[PlantType]> {
+        self.versus
+            .as_ref()
+            .map(|versus| versus.plants.as_slice())
+            .filter(|plants| !plants.is_empty())
+    }
+}
+
+impl GeneratedWaves {
+    // 从种子生成僵尸，first_wave 为第一波的序号，生存模式下逐轮累加
+    // 每波的点数随序号增加，大波僵尸为 2.5 倍并带一只摇旗僵尸
+    pub fn generate(&self, seed: u64, first_wave: u32) -> Vec<Wave> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..self.count)
+            .map(|index| {
+                let wave = first_wave + index;
+                let huge_wave = index + 1 == self.count
+                    || (self.flag_interval > 0 && (index + 1) % self.flag_interval == 0);
+
+                let mut budget = self.budget + self.growth * wave as f32;
+                if huge_wave {
+                    budget *= 2.5;
+                }
+                let mut points = budget as u32;
+
+                let mut zombies = Vec::<Zombie>::new();
+                if huge_wave {
+                    zombies.push(Zombie {
+                        zombie: ZombieType::Flag,
+                        count: 1,
+                    });
+                }
+
+                loop {
+                    let affordable = self
+                        .zombies
+                        .iter()
+                        .filter(|zombie| zombie.cost > 0 && zombie.cost <= points)
+                        .filter(|zombie| zombie.wave <= wave)
+                        .collect::<Vec<_>>();
+                    let Ok(picked) = affordable.choose_weighted(&mut rng, |zombie| zombie.weight)
+                    else {
+                        break;
+                    };
+                    points -= picked.cost;
+
+                    match zombies
+                        .iter_mut()
+                        .find(|zombie| zombie.zombie == picked.zombie && zombie.count < u8::MAX)
+                    {
+                        Some(zombie) => zombie.count += 1,
+                        None => zombies.push(Zombie {
+                            zombie: picked.zombie,
+                            count: 1,
+                        }),
+                    }
+                }
+
+                Wave {
+                    wave_type: match huge_wave {
+                        true => WaveType::HugeWave,
+                        false => WaveType::Normal,
+                    },
+                    zombies,
+                    graves: 0,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Zombie {
     #[serde(rename = "$value")]
     pub zombie: ZombieType,
@@ -176,16 +395,19 @@ pub struct Zombie {
     pub count: u8,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Wave {
     #[serde(rename = "type")]
     #[serde(default)]
     pub wave_type: WaveType,
     #[serde(rename = "Zombie")]
     pub zombies: Vec<Zombie>,
+    // 本波新生成的墓碑数量
+    #[serde(default)]
+    pub graves: u32,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
 pub enum WaveType {
     #[default]
     Normal,
@@ -212,6 +434,38 @@ const fn default_zombie_count() -> u8 {
     1
 }
 
+const fn default_survival_flags() -> u32 {
+    5
+}
+
+const fn default_brains() -> u32 {
+    150
+}
+
+const fn default_brain_interval() -> f32 {
+    5.0
+}
+
+const fn default_brain_amount() -> u32 {
+    25
+}
+
+const fn default_plant_columns() -> u8 {
+    6
+}
+
+const fn default_target_hp() -> f32 {
+    300.0
+}
+
+const fn default_flag_interval() -> u32 {
+    10
+}
+
+const fn default_weight() -> f32 {
+    1.0
+}
+
 const fn default_sunshine() -> u32 {
     50
 }
@@ -220,6 +474,10 @@ const fn default_first_wave_time() -> f32 {
     30.0
 }
 
+const fn default_grave_column() -> u8 {
+    4
+}
+
 const fn default_true() -> bool {
     true
 }
@@ -227,3 +485,106 @@ const fn default_true() -> bool {
 const fn default_false() -> bool {
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated_waves() -> GeneratedWaves {
+        GeneratedWaves {
+            count: 10,
+            flag_interval: 4,
+            budget: 3.0,
+            growth: 0.5,
+            seed: None,
+            zombies: vec![
+                GeneratedZombie {
+                    zombie: ZombieType::Basic,
+                    cost: 1,
+                    weight: 4.0,
+                    wave: 0,
+                },
+                GeneratedZombie {
+                    zombie: ZombieType::Conehead,
+                    cost: 2,
+                    weight: 2.0,
+                    wave: 0,
+                },
+                GeneratedZombie {
+                    zombie: ZombieType::Buckethead,
+                    cost: 4,
+                    weight: 1.0,
+                    wave: 5,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn same_seed_same_waves() {
+        let generated = generated_waves();
+        assert_eq!(generated.generate(42, 0), generated.generate(42, 0));
+        assert_eq!(generated.generate(42, 10), generated.generate(42, 10));
+        assert_ne!(generated.generate(42, 0), generated.generate(43, 0));
+    }
+
+    #[test]
+    fn spends_whole_budget() {
+        let generated = generated_waves();
+        for first_wave in [0, 10] {
+            for (index, wave) in generated.generate(7, first_wave).iter().enumerate() {
+                let wave_index = first_wave + index as u32;
+                let mut budget = generated.budget + generated.growth * wave_index as f32;
+                if wave.wave_type == WaveType::HugeWave {
+                    budget *= 2.5;
+                }
+
+                // 摇旗僵尸不消耗点数
+                let spent = wave
+                    .zombies
+                    .iter()
+                    .filter(|zombie| zombie.zombie != ZombieType::Flag)
+                    .map(|zombie| {
+                        let config = generated
+                            .zombies
+                            .iter()
+                            .find(|config| config.zombie == zombie.zombie)
+                            .unwrap();
+                        assert!(config.wave <= wave_index);
+                        config.cost * zombie.count as u32
+                    })
+                    .sum::<u32>();
+                // 普通僵尸只需 1 点，点数会恰好用完
+                assert_eq!(spent, budget as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_waves_follow_flag_interval() {
+        let generated = generated_waves();
+        let waves = generated.generate(7, 0);
+        assert_eq!(waves.len(), generated.count as usize);
+
+        let huge_waves = waves
+            .iter()
+            .enumerate()
+            .filter(|(_, wave)| wave.wave_type == WaveType::HugeWave)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // 每 4 波一次，最后一波总是大波僵尸
+        assert_eq!(huge_waves, vec![3, 7, 9]);
+
+        for wave in &waves {
+            let flags = wave
+                .zombies
+                .iter()
+                .filter(|zombie| zombie.zombie == ZombieType::Flag)
+                .count();
+            match wave.wave_type {
+                WaveType::HugeWave => assert_eq!(flags, 1),
+                WaveType::Normal => assert_eq!(flags, 0),
+            }
+        }
+    }
+}
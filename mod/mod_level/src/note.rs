@@ -0,0 +1,82 @@
+use std::{fs::File, ops::Deref, sync::Arc};
+
+use bevy::{prelude::*, utils::HashMap};
+use mod_plant::metadata::PlantType;
+use mod_zombie::metadata::ZombieType;
+use serde::Deserialize;
+
+use crate::{LevelRegistry, Reward};
+
+// 关卡奖励中的信件，按路径索引
+#[derive(Resource)]
+pub struct NoteRegistry(pub HashMap<String, Arc<Note>>);
+
+#[derive(Debug, Deserialize)]
+pub struct Note {
+    #[serde(rename = "Title")]
+    pub title: String,
+    // 掉落在草地上时的图片
+    #[serde(rename = "Texture")]
+    pub texture: String,
+    // 每页的内容
+    #[serde(rename = "Page")]
+    pub pages: Vec<String>,
+    // 阅读信件后解锁的内容
+    #[serde(rename = "Unlock")]
+    #[serde(default)]
+    pub unlocks: Vec<NoteUnlock>,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum NoteUnlock {
+    // 小游戏
+    Minigame,
+    // 解谜模式
+    Puzzle,
+    // 生存模式
+    Survival,
+    // 图鉴中的植物
+    Plant {
+        #[serde(rename = "type")]
+        plant: PlantType,
+    },
+    // 图鉴中的僵尸
+    Zombie {
+        #[serde(rename = "type")]
+        zombie: ZombieType,
+    },
+}
+
+// 加载关卡奖励中引用的所有信件，路径相对于 assets
+pub(crate) fn load_notes(levels: &LevelRegistry) -> NoteRegistry {
+    let notes = levels
+        .values()
+        .filter_map(|level| match &level.reward {
+            Some(Reward::Note { note_path }) => Some(note_path.clone()),
+            _ => None,
+        })
+        .map(|note_path| {
+            let mut note: Note =
+                serde_xml_rs::from_reader(File::open(format!("./assets/{note_path}")).unwrap())
+                    .unwrap();
+            // 去掉文档缩进带来的空白
+            for page in &mut note.pages {
+                *page = page.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+                *page = page.trim().to_owned();
+            }
+            debug!("loaded note {note_path}: {:?}", note);
+
+            (note_path, Arc::new(note))
+        })
+        .collect();
+
+    NoteRegistry(notes)
+}
+
+impl Deref for NoteRegistry {
+    type Target = HashMap<String, Arc<Note>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
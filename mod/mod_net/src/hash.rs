@@ -0,0 +1,80 @@
+use std::hash::{Hash, Hasher};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+// FNV-1a 哈希，用于比较双方的对局状态
+// 标准库的 DefaultHasher 不保证在不同版本与平台上结果一致，不能用于联机
+pub struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET)
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    // 整数统一按小端写入，usize 按 u64 写入，与平台无关
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+// 计算单个值的哈希
+pub fn hash_state(value: impl Hash) -> u64 {
+    let mut hasher = StateHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_fnv1a() {
+        let mut hasher = StateHasher::default();
+        hasher.write(b"");
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+
+        let mut hasher = StateHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let mut hasher = StateHasher::default();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.finish(), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn is_stable() {
+        // 修改哈希方式会导致不同版本之间无法联机，需要同时递增 PROTOCOL_VERSION
+        assert_eq!(
+            hash_state((vec![1u64, 2, 3], 50u32, 0u32)),
+            0xb8d8_1e6b_da2d_4494
+        );
+    }
+}
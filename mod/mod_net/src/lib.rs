@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+mod hash;
+mod lockstep;
+mod protocol;
+mod session;
+
+pub use hash::{hash_state, StateHasher};
+pub use lockstep::{Lockstep, INPUT_DELAY, TICK};
+pub use protocol::{VersusCommand, PROTOCOL_VERSION};
+pub use session::{NetError, NetRole, NetSession, NetStatus, TIMEOUT};
+
+// 联机对战，两端各自运行模拟，只交换玩家操作
+pub struct ModNetPlugin;
+
+// 收发消息，需要在使用 NetSession 的系统之前执行
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub struct NetSystem;
+
+impl Plugin for ModNetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            First,
+            poll_session
+                .in_set(NetSystem)
+                .run_if(resource_exists::<NetSession>),
+        );
+    }
+}
+
+fn poll_session(mut session: ResMut<NetSession>) {
+    session.poll();
+}
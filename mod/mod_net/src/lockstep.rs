@@ -0,0 +1,101 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::{NetError, NetRole, VersusCommand};
+
+// 每帧的时长，双方必须一致
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+// 操作延迟执行的帧数，用于掩盖网络延迟
+pub const INPUT_DELAY: u32 = 6;
+// 落后时最多累积的帧数，避免卡顿后连续快进
+const MAX_PENDING_TICKS: u32 = 4;
+
+// 帧同步状态，帧数从进入 Main 状态开始计算
+#[derive(Default)]
+pub struct Lockstep {
+    // 是否正在同步对局
+    pub active: bool,
+    // 下一个要执行的帧
+    pub tick: u32,
+    // 本帧是否推进模拟
+    pub stepping: bool,
+    // 本地尚未发送的操作
+    pub pending: Vec<VersusCommand>,
+    pub(crate) local: BTreeMap<u32, Vec<VersusCommand>>,
+    pub(crate) remote: BTreeMap<u32, Vec<VersusCommand>>,
+    pub(crate) local_hashes: BTreeMap<u32, u64>,
+    pub(crate) remote_hashes: BTreeMap<u32, u64>,
+    accumulated: Duration,
+    last_update: Option<Instant>,
+}
+
+impl Lockstep {
+    // 开始同步，对方可能已经发来了前几帧的操作，需要保留
+    pub(crate) fn start(&mut self) {
+        self.active = true;
+        self.tick = 0;
+        self.stepping = false;
+        self.pending.clear();
+        self.local.clear();
+        self.local_hashes.clear();
+        self.accumulated = Duration::ZERO;
+        self.last_update = None;
+    }
+
+    // 按真实时间决定本帧是否推进，双方的操作都到达后才能推进
+    pub(crate) fn update(&mut self) -> bool {
+        let now = Instant::now();
+        if let Some(last_update) = self.last_update {
+            self.accumulated =
+                (self.accumulated + (now - last_update)).min(TICK * MAX_PENDING_TICKS);
+        }
+        self.last_update = Some(now);
+
+        self.stepping = self.accumulated >= TICK
+            && self.local.contains_key(&self.tick)
+            && self.remote.contains_key(&self.tick);
+        if self.stepping {
+            self.accumulated -= TICK;
+        }
+        self.stepping
+    }
+
+    // 取出当前帧双方的操作，主机的操作在前，忽略不属于对方的操作
+    pub(crate) fn take_commands(&mut self, role: NetRole) -> Vec<VersusCommand> {
+        let local = self.local.remove(&self.tick).unwrap_or_default();
+        let remote = self.remote.remove(&self.tick).unwrap_or_default();
+        let (host, join) = match role {
+            NetRole::Host => (local, remote),
+            NetRole::Join => (remote, local),
+        };
+
+        host.into_iter()
+            .filter(|command| command.role() == NetRole::Host)
+            .chain(
+                join.into_iter()
+                    .filter(|command| command.role() == NetRole::Join),
+            )
+            .collect()
+    }
+
+    // 比较双方都已计算的帧
+    pub(crate) fn check_hashes(&mut self) -> Result<(), NetError> {
+        while let Some((&tick, &local)) = self.local_hashes.first_key_value() {
+            let Some(remote) = self.remote_hashes.remove(&tick) else {
+                break;
+            };
+            self.local_hashes.remove(&tick);
+
+            if local != remote {
+                return Err(NetError::Desync {
+                    tick,
+                    local,
+                    remote,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use mod_plant::metadata::PlantType;
+use mod_zombie::metadata::ZombieType;
+use serde::{Deserialize, Serialize};
+
+use crate::NetRole;
+
+// 协议版本，修改消息格式时需要递增
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// 对战中的玩家操作，联机时在双方的同一帧执行
+#[derive(Debug, Clone, PartialEq, Event, Serialize, Deserialize)]
+pub enum VersusCommand {
+    // 植物方种植，坐标为格子的列与路
+    PlantSeed {
+        plant: PlantType,
+        column: u8,
+        lane: u8,
+    },
+    // 植物方收集阳光，坐标为阳光的位置
+    CollectSunshine {
+        x: f32,
+        y: f32,
+    },
+    // 僵尸方放置僵尸，坐标为格子的列与路
+    PlaceZombie {
+        zombie: ZombieType,
+        column: u8,
+        lane: u8,
+    },
+}
+
+impl VersusCommand {
+    // 发出该操作的一方
+    pub fn role(&self) -> NetRole {
+        match self {
+            VersusCommand::PlantSeed { .. } | VersusCommand::CollectSunshine { .. } => {
+                NetRole::Host
+            }
+            VersusCommand::PlaceZombie { .. } => NetRole::Join,
+        }
+    }
+}
+
+// 消息，每条为一行 JSON
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum NetMessage {
+    // 建立连接后双方各发送一次，随机数种子以主机为准
+    Hello {
+        version: u32,
+        seed: u64,
+    },
+    // 保持连接，没有其他消息时定期发送
+    Ping,
+    // 在该帧执行的操作，每一帧都会发送，没有操作时为空
+    Commands {
+        tick: u32,
+        commands: Vec<VersusCommand>,
+    },
+    // 执行完该帧后的状态哈希
+    Hash {
+        tick: u32,
+        hash: u64,
+    },
+}
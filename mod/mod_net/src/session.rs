@@ -0,0 +1,460 @@
+use std::{
+    fmt,
+    io::{self, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+
+use crate::{protocol::NetMessage, Lockstep, VersusCommand, INPUT_DELAY, PROTOCOL_VERSION};
+
+// 连接对方的超时时间
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// 超过该时间没有收到任何消息则视为断开
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+// 没有其他消息时发送 Ping 的间隔
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+// 主机为植物方，加入的一方为僵尸方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetRole {
+    Host,
+    Join,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetStatus {
+    // 主机等待加入
+    Listening,
+    // 正在连接主机
+    Connecting,
+    // 已连接，等待对方的 Hello
+    Handshake,
+    // 可以开始对局
+    Ready,
+    Error(NetError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetError {
+    Io(String),
+    // 长时间没有收到消息
+    Timeout,
+    // 对方关闭了连接
+    Disconnected,
+    // 双方的协议版本不同
+    Version { local: u32, remote: u32 },
+    // 同一帧的状态哈希不同
+    Desync { tick: u32, local: u64, remote: u64 },
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "网络错误：{err}"),
+            NetError::Timeout => write!(f, "连接超时"),
+            NetError::Disconnected => write!(f, "对方已断开连接"),
+            NetError::Version { local, remote } => {
+                write!(f, "版本不一致（本地 {local}，对方 {remote}）")
+            }
+            NetError::Desync {
+                tick,
+                local,
+                remote,
+            } => write!(
+                f,
+                "对局不同步（第 {tick} 帧，本地 {local:016x}，对方 {remote:016x}）"
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        NetError::Io(err.to_string())
+    }
+}
+
+// 联机会话，由大厅创建，离开游戏场景时移除
+#[derive(Resource)]
+pub struct NetSession {
+    pub role: NetRole,
+    pub status: NetStatus,
+    // 随机数种子，以主机为准
+    pub seed: u64,
+    pub lockstep: Lockstep,
+    listener: Option<TcpListener>,
+    connecting: Option<JoinHandle<io::Result<TcpStream>>>,
+    stream: Option<TcpStream>,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    last_received: Instant,
+    last_sent: Instant,
+}
+
+impl NetSession {
+    fn new(role: NetRole, status: NetStatus, seed: u64) -> Self {
+        Self {
+            role,
+            status,
+            seed,
+            lockstep: Lockstep::default(),
+            listener: None,
+            connecting: None,
+            stream: None,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            last_received: Instant::now(),
+            last_sent: Instant::now(),
+        }
+    }
+
+    // 创建房间，在该地址等待对方加入
+    pub fn host(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+
+        let mut session = Self::new(NetRole::Host, NetStatus::Listening, rand::random());
+        session.listener = Some(listener);
+        Ok(session)
+    }
+
+    // 加入房间，在后台线程中连接，避免阻塞画面
+    pub fn join(addr: &str) -> io::Result<Self> {
+        let addr = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "no address"))?;
+
+        let mut session = Self::new(NetRole::Join, NetStatus::Connecting, 0);
+        session.connecting = Some(thread::spawn(move || {
+            TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        }));
+        Ok(session)
+    }
+
+    pub fn error(&self) -> Option<&NetError> {
+        match &self.status {
+            NetStatus::Error(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    // 接受连接、收发消息并检查超时，每帧调用
+    pub fn poll(&mut self) {
+        if self.error().is_some() {
+            return;
+        }
+        if let Err(err) = self.try_poll() {
+            self.fail(err);
+        }
+    }
+
+    // 开始帧同步，先发送延迟帧内的空操作
+    pub fn start_lockstep(&mut self) {
+        self.lockstep.start();
+        for tick in 0..INPUT_DELAY {
+            self.lockstep.local.insert(tick, Vec::new());
+            self.send(NetMessage::Commands {
+                tick,
+                commands: Vec::new(),
+            });
+        }
+    }
+
+    // 按真实时间决定本帧是否推进模拟
+    pub fn update_lockstep(&mut self) -> bool {
+        if self.status != NetStatus::Ready {
+            self.lockstep.stepping = false;
+            return false;
+        }
+        self.lockstep.update()
+    }
+
+    // 发送本帧的本地操作，在延迟帧后执行
+    pub fn send_pending(&mut self) {
+        let tick = self.lockstep.tick + INPUT_DELAY;
+        let commands = std::mem::take(&mut self.lockstep.pending);
+        self.lockstep.local.insert(tick, commands.clone());
+        self.send(NetMessage::Commands { tick, commands });
+    }
+
+    // 取出当前帧需要执行的操作
+    pub fn take_commands(&mut self) -> Vec<VersusCommand> {
+        self.lockstep.take_commands(self.role)
+    }
+
+    // 记录当前帧的状态哈希，进入下一帧
+    pub fn finish_tick(&mut self, hash: u64) {
+        let tick = self.lockstep.tick;
+        self.lockstep.local_hashes.insert(tick, hash);
+        self.send(NetMessage::Hash { tick, hash });
+        self.lockstep.tick += 1;
+
+        if let Err(err) = self.lockstep.check_hashes() {
+            self.fail(err);
+        }
+    }
+
+    fn fail(&mut self, err: NetError) {
+        error!("net session failed: {err}");
+        self.status = NetStatus::Error(err);
+        self.lockstep.stepping = false;
+        self.listener = None;
+        self.stream = None;
+    }
+
+    fn try_poll(&mut self) -> Result<(), NetError> {
+        // 主机等待加入
+        if let Some(listener) = &self.listener {
+            match listener.accept() {
+                Ok((stream, addr)) => {
+                    info!("player joined from {addr}");
+                    self.listener = None;
+                    self.connected(stream)?;
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        // 等待连接线程结束
+        if self
+            .connecting
+            .as_ref()
+            .is_some_and(JoinHandle::is_finished)
+        {
+            let Some(connecting) = self.connecting.take() else {
+                return Ok(());
+            };
+            let stream = connecting.join().map_err(|_| NetError::Disconnected)??;
+            self.connected(stream)?;
+        }
+
+        if self.stream.is_none() {
+            return Ok(());
+        }
+
+        self.receive()?;
+        if self.last_sent.elapsed() >= PING_INTERVAL {
+            self.send(NetMessage::Ping);
+        }
+        self.flush()?;
+
+        if self.last_received.elapsed() >= TIMEOUT {
+            return Err(NetError::Timeout);
+        }
+        self.lockstep.check_hashes()
+    }
+
+    fn connected(&mut self, stream: TcpStream) -> Result<(), NetError> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+
+        self.stream = Some(stream);
+        self.status = NetStatus::Handshake;
+        self.last_received = Instant::now();
+        self.send(NetMessage::Hello {
+            version: PROTOCOL_VERSION,
+            seed: self.seed,
+        });
+        Ok(())
+    }
+
+    fn send(&mut self, message: NetMessage) {
+        if let Err(err) = serde_json::to_writer(&mut self.outgoing, &message) {
+            error!("failed to encode {message:?}: {err}");
+            return;
+        }
+        self.outgoing.push(b'\n');
+        self.last_sent = Instant::now();
+    }
+
+    fn flush(&mut self) -> Result<(), NetError> {
+        let Some(stream) = &mut self.stream else {
+            return Ok(());
+        };
+
+        while !self.outgoing.is_empty() {
+            match stream.write(&self.outgoing) {
+                Ok(0) => return Err(NetError::Disconnected),
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<(), NetError> {
+        let Some(stream) = &mut self.stream else {
+            return Ok(());
+        };
+
+        let mut buffer = [0; 4096];
+        loop {
+            match stream.read(&mut buffer) {
+                Ok(0) => return Err(NetError::Disconnected),
+                Ok(n) => {
+                    self.incoming.extend_from_slice(&buffer[..n]);
+                    self.last_received = Instant::now();
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        // 每行一条消息
+        while let Some(end) = self.incoming.iter().position(|byte| *byte == b'\n') {
+            let line = self.incoming.drain(..=end).collect::<Vec<_>>();
+            let message = serde_json::from_slice::<NetMessage>(&line[..end])
+                .map_err(|err| NetError::Io(err.to_string()))?;
+            self.handle(message)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, message: NetMessage) -> Result<(), NetError> {
+        match message {
+            NetMessage::Hello { version, seed } => {
+                if version != PROTOCOL_VERSION {
+                    return Err(NetError::Version {
+                        local: PROTOCOL_VERSION,
+                        remote: version,
+                    });
+                }
+                if self.role == NetRole::Join {
+                    self.seed = seed;
+                }
+                self.status = NetStatus::Ready;
+            }
+            NetMessage::Ping => {}
+            NetMessage::Commands { tick, commands } => {
+                self.lockstep.remote.insert(tick, commands);
+            }
+            NetMessage::Hash { tick, hash } => {
+                self.lockstep.remote_hashes.insert(tick, hash);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mod_plant::metadata::PlantType;
+    use mod_zombie::metadata::ZombieType;
+
+    use super::*;
+
+    // 轮流收发双方的消息，直到满足条件
+    fn poll_until(
+        host: &mut NetSession,
+        join: &mut NetSession,
+        done: impl Fn(&NetSession, &NetSession) -> bool,
+    ) {
+        let deadline = Instant::now() + CONNECT_TIMEOUT;
+        while !done(host, join) {
+            assert!(Instant::now() < deadline, "loopback timed out");
+            host.poll();
+            join.poll();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn connect() -> (NetSession, NetSession) {
+        let mut host = NetSession::host("127.0.0.1:0").unwrap();
+        let addr = host.listener.as_ref().unwrap().local_addr().unwrap();
+        let mut join = NetSession::join(&addr.to_string()).unwrap();
+        poll_until(&mut host, &mut join, |host, join| {
+            host.status == NetStatus::Ready && join.status == NetStatus::Ready
+        });
+        (host, join)
+    }
+
+    #[test]
+    fn loopback_lockstep() {
+        let (mut host, mut join) = connect();
+        assert_eq!(host.seed, join.seed);
+
+        host.start_lockstep();
+        join.start_lockstep();
+
+        let plant = VersusCommand::PlantSeed {
+            plant: PlantType::PeaShooter,
+            column: 1,
+            lane: 2,
+        };
+        let zombie = VersusCommand::PlaceZombie {
+            zombie: ZombieType::Basic,
+            column: 8,
+            lane: 3,
+        };
+        host.lockstep.pending.push(plant.clone());
+        join.lockstep.pending.push(zombie.clone());
+
+        for tick in 0..INPUT_DELAY * 2 {
+            host.send_pending();
+            join.send_pending();
+            poll_until(&mut host, &mut join, |host, join| {
+                host.lockstep.remote.contains_key(&tick) && join.lockstep.remote.contains_key(&tick)
+            });
+
+            // 双方在同一帧得到相同的操作
+            let commands = host.take_commands();
+            assert_eq!(commands, join.take_commands());
+            if tick == INPUT_DELAY {
+                assert_eq!(commands, vec![plant.clone(), zombie.clone()]);
+            } else {
+                assert!(commands.is_empty());
+            }
+
+            host.finish_tick(u64::from(tick));
+            join.finish_tick(u64::from(tick));
+        }
+
+        // 所有帧的哈希都比较完毕
+        poll_until(&mut host, &mut join, |host, join| {
+            host.lockstep.local_hashes.is_empty() && join.lockstep.local_hashes.is_empty()
+        });
+        assert_eq!(host.status, NetStatus::Ready);
+        assert_eq!(join.status, NetStatus::Ready);
+    }
+
+    #[test]
+    fn loopback_desync() {
+        let (mut host, mut join) = connect();
+        host.start_lockstep();
+        join.start_lockstep();
+
+        host.finish_tick(1);
+        join.finish_tick(2);
+        poll_until(&mut host, &mut join, |host, join| {
+            host.error().is_some() || join.error().is_some()
+        });
+
+        // 先发现不同步的一方断开连接，另一方可能只收到断开
+        let host_desync = NetError::Desync {
+            tick: 0,
+            local: 1,
+            remote: 2,
+        };
+        let join_desync = NetError::Desync {
+            tick: 0,
+            local: 2,
+            remote: 1,
+        };
+        assert!(host.error() == Some(&host_desync) || join.error() == Some(&join_desync));
+        for (session, desync) in [(&host, host_desync), (&join, join_desync)] {
+            if let Some(err) = session.error() {
+                assert!(*err == desync || *err == NetError::Disconnected, "{err}");
+            }
+        }
+    }
+}
@@ -74,6 +74,13 @@ pub struct AnimPlantDamage1Tag;
 #[derive(Component)]
 pub struct AnimPlantDamage2Tag;
 
+// 睡眠动画，循环播放直到移除
+#[derive(Component)]
+pub struct AnimPlantSleepTag;
+
+#[derive(Default, Component)]
+pub(crate) struct AnimPlantSleepPlayingTag;
+
 #[derive(Bundle)]
 pub struct PlantSeedBundle {
     // 植物类型
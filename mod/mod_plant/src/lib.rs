@@ -2,7 +2,7 @@ use std::{fs::File, sync::Arc};
 
 use bevy::prelude::*;
 use bevy_spine::SkeletonData;
-use metadata::{PlantRegistry, Plants};
+use metadata::{PlantRegistry, Plants, ProjectileRegistry, Projectiles};
 
 pub mod components;
 pub mod metadata;
@@ -13,8 +13,10 @@ pub struct ModPlantPlugin;
 impl Plugin for ModPlantPlugin {
     fn build(&self, app: &mut App) {
         let plant_registry = load_registry(app.world_mut());
+        let projectile_registry = load_projectile_registry();
 
         app.insert_resource(plant_registry)
+            .insert_resource(projectile_registry)
             .add_systems(PreUpdate, systems::update_seed_hover)
             .add_systems(
                 Update,
@@ -32,6 +34,8 @@ impl Plugin for ModPlantPlugin {
                     systems::start_instant_anim,
                     systems::start_damage1_anim,
                     systems::start_damage2_anim,
+                    systems::start_sleep_anim,
+                    systems::stop_sleep_anim,
                 ),
             );
     }
@@ -59,3 +63,17 @@ fn load_registry(world: &mut World) -> PlantRegistry {
             .collect(),
     )
 }
+
+fn load_projectile_registry() -> ProjectileRegistry {
+    let projectiles: Projectiles =
+        serde_xml_rs::from_reader(File::open("./assets/data/projectiles.xml").unwrap()).unwrap();
+    debug!("loaded projectile infos: {projectiles:?}");
+
+    ProjectileRegistry(
+        projectiles
+            .projectiles
+            .into_iter()
+            .map(|projectile| (projectile.id, Arc::new(projectile)))
+            .collect(),
+    )
+}
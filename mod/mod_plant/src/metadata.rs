@@ -3,9 +3,9 @@ use std::{ops::Deref, sync::Arc};
 
 use bevy::{prelude::*, utils::HashMap};
 use bevy_spine::SkeletonData;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum PlantType {
     // 豌豆射手
     PeaShooter,
@@ -120,6 +120,15 @@ pub(super) struct Plants {
 #[derive(Debug, Resource, Default)]
 pub struct PlantRegistry(pub HashMap<PlantType, Arc<PlantInfo>>);
 
+#[derive(Debug, Deserialize)]
+pub(super) struct Projectiles {
+    #[serde(rename = "Projectile")]
+    pub(super) projectiles: Vec<ProjectileInfo>,
+}
+
+#[derive(Debug, Resource, Default)]
+pub struct ProjectileRegistry(pub HashMap<ProjectileType, Arc<ProjectileInfo>>);
+
 #[derive(Debug, Deserialize)]
 pub struct PlantInfo {
     pub id: PlantType,
@@ -154,6 +163,22 @@ pub struct PlantInfo {
     #[serde(rename = "Bowling")]
     #[serde(default)]
     pub bowling: Option<PlantBowling>,
+    // 高大植物，阻挡僵尸跳跃
+    #[serde(rename = "Tall")]
+    #[serde(default = "default_false")]
+    pub tall: bool,
+    // 升级植物，只能种在对应的植物上
+    #[serde(rename = "UpgradeFrom")]
+    #[serde(default)]
+    pub upgrade_from: Option<PlantType>,
+    // 夜行植物，白天种植时会睡着
+    #[serde(rename = "Nocturnal")]
+    #[serde(default = "default_false")]
+    pub nocturnal: bool,
+    // 种在墓碑上，经过该时间（秒）后吞掉墓碑并消失
+    #[serde(rename = "ConsumeGrave")]
+    #[serde(default)]
+    pub consume_grave: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -198,6 +223,40 @@ pub enum InstantEffectType {
         #[serde(default = "default_explode_damage")]
         damage: f32,
     },
+    // 冰冻范围内的僵尸
+    Freeze {
+        radius: f32,
+        duration: f32,
+    },
+    // 在所在格子留下弹坑，持续时间内无法种植
+    Crater {
+        #[serde(default = "default_crater_duration")]
+        duration: f32,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusEffect {
+    #[serde(rename = "Type")]
+    pub effect_type: StatusEffectType,
+    #[serde(rename = "Duration")]
+    pub duration: f32,
+    // 减速时为速度倍率，灼烧时为每秒伤害
+    #[serde(rename = "Strength")]
+    #[serde(default)]
+    pub strength: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum StatusEffectType {
+    // 减速
+    Slow,
+    // 冰冻，无法移动和啃食
+    Freeze,
+    // 黄油，无法移动和啃食
+    Butter,
+    // 灼烧，持续受到伤害
+    Burn,
 }
 
 #[derive(Debug, Deserialize)]
@@ -329,7 +388,7 @@ pub struct ShootProjectile {
     pub offset_y: f32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum ProjectileType {
     Pea,
     SnowPea,
@@ -339,9 +398,43 @@ pub enum ProjectileType {
 
 #[derive(Debug, Deserialize)]
 pub enum ProjectileTrack {
-    Line { direction: f32 },
+    Line {
+        direction: f32,
+    },
     Throw,
-    Follow,
+    // 追踪，turn_rate 为每秒最大转向角度（弧度）
+    Follow {
+        #[serde(default = "default_follow_turn_rate")]
+        turn_rate: f32,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectileInfo {
+    pub id: ProjectileType,
+    #[serde(rename = "Texture")]
+    pub texture: String,
+    #[serde(rename = "Damage")]
+    pub damage: f32,
+    // 命中时施加的状态效果
+    #[serde(rename = "Effect")]
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+    // 溅射伤害
+    #[serde(rename = "Splash")]
+    pub splash: Option<ProjectileSplash>,
+    // 可穿透的僵尸数量
+    #[serde(rename = "Pierce")]
+    #[serde(default)]
+    pub pierce: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectileSplash {
+    #[serde(rename = "Radius")]
+    pub radius: f32,
+    #[serde(rename = "Damage")]
+    pub damage: f32,
 }
 
 impl Deref for PlantRegistry {
@@ -352,6 +445,14 @@ impl Deref for PlantRegistry {
     }
 }
 
+impl Deref for ProjectileRegistry {
+    type Target = HashMap<ProjectileType, Arc<ProjectileInfo>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 impl Default for PlantOn {
     fn default() -> Self {
         Self {
@@ -378,6 +479,10 @@ const fn f32_infinity() -> f32 {
     f32::INFINITY
 }
 
+const fn default_crater_duration() -> f32 {
+    180.0
+}
+
 const fn default_explode_damage() -> f32 {
     1800.
 }
@@ -385,3 +490,7 @@ const fn default_explode_damage() -> f32 {
 const fn default_plant_hp() -> f32 {
     200.
 }
+
+const fn default_follow_turn_rate() -> f32 {
+    6.
+}
@@ -6,16 +6,16 @@ use bevy::{
 use bevy_spine::prelude::*;
 use consts::anim::{
     INDEX_PLANT_DAMAGE_1, INDEX_PLANT_DAMAGE_2, INDEX_PLANT_INSTANT, INDEX_PLANT_PRODUCE,
-    INDEX_PLANT_SHOOT, NAME_PLANT_DAMAGE_1, NAME_PLANT_DAMAGE_2, NAME_PLANT_INSTANT,
-    NAME_PLANT_PRODUCE, NAME_PLANT_SHOOT,
+    INDEX_PLANT_SHOOT, INDEX_PLANT_SLEEP, NAME_PLANT_DAMAGE_1, NAME_PLANT_DAMAGE_2,
+    NAME_PLANT_INSTANT, NAME_PLANT_PRODUCE, NAME_PLANT_SHOOT, NAME_PLANT_SLEEP,
 };
-use fw_actor::oneshot_anim;
+use fw_actor::{looping_anim, oneshot_anim};
 use fw_cursor::CursorPosition;
 
 use crate::components::{
     AnimPlantDamage1Tag, AnimPlantDamage2Tag, AnimPlantInstantTag, AnimPlantProduceTag,
-    AnimPlantShootTag, CooldownOverlay, PlantCooldown, PlantMetaData, PlantSeed, PlantUsable,
-    SeedHover, SunshineVisibility, UnusedOverlay,
+    AnimPlantShootTag, AnimPlantSleepPlayingTag, AnimPlantSleepTag, CooldownOverlay, PlantCooldown,
+    PlantMetaData, PlantSeed, PlantUsable, SeedHover, SunshineVisibility, UnusedOverlay,
 };
 
 #[allow(clippy::type_complexity)]
@@ -264,3 +264,12 @@ oneshot_anim!(
     INDEX_PLANT_DAMAGE_2,
     NAME_PLANT_DAMAGE_2
 );
+
+looping_anim!(
+    AnimPlantSleepTag,
+    AnimPlantSleepPlayingTag,
+    start_sleep_anim,
+    stop_sleep_anim,
+    INDEX_PLANT_SLEEP,
+    NAME_PLANT_SLEEP
+);
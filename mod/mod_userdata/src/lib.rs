@@ -1,15 +1,39 @@
 use bevy::{prelude::*, utils::HashSet};
+use mod_item::{Item, ItemType};
 use mod_plant::metadata::PlantType;
+use mod_zombie::metadata::ZombieType;
+use serde::{Deserialize, Serialize};
+
+pub mod save;
 
 pub struct ModUserdataPlugin;
 
+// 种子槽的最大数量
+pub const MAX_PLANT_SOLT_COUNT: usize = 10;
+
 impl Plugin for ModUserdataPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<UserData>();
+        let (profiles, userdata) = save::load_profiles();
+
+        app.insert_resource(profiles)
+            .insert_resource(userdata)
+            .add_event::<save::ProfileEvent>()
+            .add_systems(
+                Last,
+                (
+                    save::handle_profile_event,
+                    // 刚插入的存档不需要写回，避免启动时覆盖读取失败的存档
+                    save::save_userdata.run_if(
+                        resource_changed::<UserData>.and_then(not(resource_added::<UserData>)),
+                    ),
+                )
+                    .chain(),
+            );
     }
 }
 
-#[derive(Debug, Resource)]
+#[derive(Debug, Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UserData {
     pub unlock_plugins: HashSet<PlantType>,
     pub plant_solt_count: usize,
@@ -23,6 +47,12 @@ pub struct UserData {
     pub unlock_shop: bool,
     pub unlock_shop_taco_upgrade: bool,
     pub unlock_zen_garden: bool,
+    // 无尽生存到达的最高旗数
+    pub survival_endless_best: u32,
+    // 遇到过的僵尸，显示在图鉴中
+    pub seen_zombies: HashSet<ZombieType>,
+    // 金币，用于在商店购买物品
+    pub coins: u32,
 }
 
 impl Default for UserData {
@@ -40,6 +70,58 @@ impl Default for UserData {
             unlock_shop: false,
             unlock_shop_taco_upgrade: false,
             unlock_zen_garden: false,
+            survival_endless_best: 0,
+            seen_zombies: HashSet::new(),
+            coins: 0,
+        }
+    }
+}
+
+impl UserData {
+    // 获得物品，关卡奖励与商店购买共用
+    pub fn obtain_item(&mut self, item: &Item) {
+        match item.id {
+            ItemType::Shovel => {
+                self.unlock_shovel = true;
+            }
+            ItemType::Almanac => {
+                self.unlock_almanac = true;
+            }
+            ItemType::Key => {
+                self.unlock_shop = true;
+            }
+            ItemType::Taco => {
+                self.unlock_shop_taco_upgrade = true;
+            }
+            ItemType::WateringCan => {
+                self.unlock_zen_garden = true;
+            }
+            ItemType::SeedSlot => {
+                self.plant_solt_count = (self.plant_solt_count + 1).min(MAX_PLANT_SOLT_COUNT);
+            }
+            ItemType::GatlingPea | ItemType::TwinSunflower => {}
+        }
+
+        if let Some(plant) = item.plant {
+            self.unlock_plugins.insert(plant);
         }
     }
+
+    // 是否已经拥有该物品，拥有后不能再次购买
+    pub fn owns_item(&self, item: &Item) -> bool {
+        let owned = match item.id {
+            ItemType::Shovel => self.unlock_shovel,
+            ItemType::Almanac => self.unlock_almanac,
+            ItemType::Key => self.unlock_shop,
+            ItemType::Taco => self.unlock_shop_taco_upgrade,
+            ItemType::WateringCan => self.unlock_zen_garden,
+            ItemType::SeedSlot => self.plant_solt_count >= MAX_PLANT_SOLT_COUNT,
+            ItemType::GatlingPea | ItemType::TwinSunflower => false,
+        };
+
+        owned
+            || item
+                .plant
+                .is_some_and(|plant| self.unlock_plugins.contains(&plant))
+    }
 }
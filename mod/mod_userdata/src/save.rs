@@ -0,0 +1,373 @@
+use std::{
+    fs::{self, File},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::UserData;
+
+// 当前存档版本，修改 UserData 字段含义时需要递增并在 MIGRATIONS 中补充迁移
+pub const SAVE_VERSION: u32 = 1;
+
+// 存档迁移，MIGRATIONS[n - 1] 负责将版本 n 的存档迁移到版本 n + 1
+// 仅新增字段时不需要迁移，缺失的字段会使用 UserData::default() 中的值
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[];
+
+// 首次启动时创建的玩家名称
+const DEFAULT_PROFILE_NAME: &str = "Player";
+
+// 玩家列表
+#[derive(Debug, Resource, Clone, Serialize, Deserialize)]
+pub struct UserProfiles {
+    pub version: u32,
+    pub current: u32,
+    pub next_id: u32,
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+// 玩家管理
+#[derive(Debug, Event)]
+pub enum ProfileEvent {
+    Create(String),
+    Switch(String),
+    Rename { from: String, to: String },
+    Delete(String),
+}
+
+// 存档无法迁移到当前版本的原因
+#[derive(Debug, PartialEq)]
+enum MigrateError {
+    // 不是有效的存档格式
+    Format,
+    // 缺少版本号
+    MissingVersion,
+    // 没有从该版本开始的迁移
+    Unsupported(u32),
+    // 迁移后的数据无法读取
+    Data(String),
+}
+
+#[derive(Serialize)]
+struct SaveFile<'a> {
+    version: u32,
+    data: &'a UserData,
+}
+
+impl UserProfiles {
+    pub fn current_profile(&self) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id == self.current)
+    }
+
+    fn find(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    fn create(&mut self, name: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.profiles.push(Profile { id, name });
+        id
+    }
+}
+
+pub(crate) fn load_profiles() -> (UserProfiles, UserData) {
+    let mut profiles = read_profiles().unwrap_or_else(|| UserProfiles {
+        version: SAVE_VERSION,
+        current: 0,
+        next_id: 0,
+        profiles: Vec::new(),
+    });
+
+    // 当前玩家不存在时使用第一个玩家，没有玩家时创建默认玩家
+    if profiles.current_profile().is_none() {
+        let id = match profiles.profiles.first() {
+            Some(profile) => profile.id,
+            None => profiles.create(DEFAULT_PROFILE_NAME.to_string()),
+        };
+        profiles.current = id;
+        write_profiles(&profiles);
+    }
+
+    // 玩家列表丢失时原有的存档仍然会被读取，不会被默认存档覆盖
+    let userdata = read_userdata(profiles.current);
+    debug!("load profiles: {profiles:?}");
+
+    (profiles, userdata)
+}
+
+// 读取玩家列表，无法读取的文件会先备份
+fn read_profiles() -> Option<UserProfiles> {
+    let path = profiles_path();
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return None,
+        Err(err) => {
+            warn!("failed to open profiles: {err}");
+            backup_save(&path);
+            return None;
+        }
+    };
+
+    match serde_json::from_reader::<_, UserProfiles>(file) {
+        Ok(profiles) => Some(profiles),
+        Err(err) => {
+            warn!("failed to read profiles: {err}");
+            backup_save(&path);
+            None
+        }
+    }
+}
+
+pub(crate) fn handle_profile_event(
+    mut events: EventReader<ProfileEvent>,
+    mut profiles: ResMut<UserProfiles>,
+    mut userdata: ResMut<UserData>,
+) {
+    for event in events.read() {
+        match event {
+            ProfileEvent::Create(name) => {
+                if profiles.find(name).is_some() {
+                    warn!("profile {name} already exists");
+                    continue;
+                }
+
+                let id = profiles.create(name.clone());
+                profiles.current = id;
+                // 玩家列表丢失后重新分配的编号可能已有存档
+                let path = userdata_path(id);
+                if path.exists() {
+                    backup_save(&path);
+                }
+                *userdata = UserData::default();
+            }
+            ProfileEvent::Switch(name) => {
+                let Some(profile) = profiles.find(name) else {
+                    warn!("profile {name} not found");
+                    continue;
+                };
+
+                let id = profile.id;
+                profiles.current = id;
+                *userdata = read_userdata(id);
+            }
+            ProfileEvent::Rename { from, to } => {
+                if profiles.find(to).is_some() {
+                    warn!("profile {to} already exists");
+                    continue;
+                }
+                let Some(profile) = profiles.profiles.iter_mut().find(|p| &p.name == from) else {
+                    warn!("profile {from} not found");
+                    continue;
+                };
+
+                profile.name = to.clone();
+            }
+            ProfileEvent::Delete(name) => {
+                let Some(profile) = profiles.find(name) else {
+                    warn!("profile {name} not found");
+                    continue;
+                };
+
+                let id = profile.id;
+                profiles.profiles.retain(|profile| profile.id != id);
+                if let Err(err) = fs::remove_file(userdata_path(id)) {
+                    warn!("failed to remove save of profile {name}: {err}");
+                }
+
+                if profiles.current == id {
+                    let id = match profiles.profiles.first() {
+                        Some(profile) => profile.id,
+                        None => profiles.create(DEFAULT_PROFILE_NAME.to_string()),
+                    };
+                    profiles.current = id;
+                    *userdata = read_userdata(profiles.current);
+                }
+            }
+        }
+
+        write_profiles(&profiles);
+    }
+}
+
+pub(crate) fn save_userdata(profiles: Res<UserProfiles>, userdata: Res<UserData>) {
+    write_userdata(profiles.current, &userdata);
+}
+
+// 读取存档，无法完整读取的存档会先备份，避免之后被覆盖
+fn read_userdata(id: u32) -> UserData {
+    let path = userdata_path(id);
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return UserData::default(),
+        Err(err) => {
+            warn!("failed to open save of profile {id}: {err}");
+            backup_save(&path);
+            return UserData::default();
+        }
+    };
+
+    let value = match serde_json::from_reader::<_, Value>(file) {
+        Ok(value) => value,
+        Err(err) => {
+            warn!("failed to read save of profile {id}: {err}");
+            backup_save(&path);
+            return UserData::default();
+        }
+    };
+
+    match migrate(value) {
+        Ok((data, version)) => {
+            // 新版本的存档中未知的字段会丢失
+            if version > SAVE_VERSION {
+                warn!("save version {version} is newer than {SAVE_VERSION}, unknown fields are ignored");
+                backup_save(&path);
+            }
+            data
+        }
+        Err(err) => {
+            warn!("failed to migrate save of profile {id}: {err:?}");
+            backup_save(&path);
+            UserData::default()
+        }
+    }
+}
+
+// 迁移到当前版本，返回存档数据与存档原本的版本
+fn migrate(value: Value) -> Result<(UserData, u32), MigrateError> {
+    let Value::Object(mut save) = value else {
+        return Err(MigrateError::Format);
+    };
+    let version = save
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or(MigrateError::MissingVersion)? as u32;
+    let Some(Value::Object(mut data)) = save.remove("data") else {
+        return Err(MigrateError::Format);
+    };
+
+    for from in version..SAVE_VERSION {
+        let migration = from
+            .checked_sub(1)
+            .and_then(|index| MIGRATIONS.get(index as usize))
+            .ok_or(MigrateError::Unsupported(version))?;
+        migration(&mut data);
+    }
+
+    let userdata = serde_json::from_value(Value::Object(data))
+        .map_err(|err| MigrateError::Data(err.to_string()))?;
+    Ok((userdata, version))
+}
+
+// 复制一份存档，文件名中带有时间，不会覆盖之前的备份
+fn backup_save(path: &Path) {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let backup_path = path.with_extension(format!("{time}.bak"));
+    match fs::copy(path, &backup_path) {
+        Ok(_) => warn!("save backed up to {}", backup_path.display()),
+        Err(err) => error!("failed to back up {}: {err}", path.display()),
+    }
+}
+
+fn write_profiles(profiles: &UserProfiles) {
+    write_json(&profiles_path(), profiles);
+}
+
+fn write_userdata(id: u32, userdata: &UserData) {
+    write_json(
+        &userdata_path(id),
+        &SaveFile {
+            version: SAVE_VERSION,
+            data: userdata,
+        },
+    );
+}
+
+// 先写入临时文件再替换，避免写入过程中退出导致存档损坏
+fn write_json<T: Serialize>(path: &Path, value: &T) {
+    let temp_path = path.with_extension("tmp");
+    let result = fs::create_dir_all(save_dir())
+        .and_then(|_| File::create(&temp_path))
+        .and_then(|file| serde_json::to_writer_pretty(file, value).map_err(Into::into))
+        .and_then(|_| fs::rename(&temp_path, path));
+
+    if let Err(err) = result {
+        error!("failed to write {}: {err}", path.display());
+    }
+}
+
+fn save_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pvz")
+        .join("userdata")
+}
+
+fn profiles_path() -> PathBuf {
+    save_dir().join("users.json")
+}
+
+fn userdata_path(id: u32) -> PathBuf {
+    save_dir().join(format!("user{id}.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn migrate_missing_version() {
+        let save = json!({ "data": { "coins": 10 } });
+        assert_eq!(migrate(save).err(), Some(MigrateError::MissingVersion));
+    }
+
+    #[test]
+    fn migrate_version_zero() {
+        let save = json!({ "version": 0, "data": { "coins": 10 } });
+        assert_eq!(migrate(save).err(), Some(MigrateError::Unsupported(0)));
+    }
+
+    #[test]
+    fn migrate_current_version() {
+        let save = json!({
+            "version": SAVE_VERSION,
+            "data": { "coins": 10, "adventure_progress": 3 },
+        });
+        let (userdata, version) = migrate(save).unwrap();
+        assert_eq!(version, SAVE_VERSION);
+        assert_eq!(userdata.coins, 10);
+        assert_eq!(userdata.adventure_progress, 3);
+        // 缺失的字段使用默认值
+        assert_eq!(
+            userdata.plant_solt_count,
+            UserData::default().plant_solt_count
+        );
+    }
+
+    #[test]
+    fn migrate_future_version() {
+        let save = json!({
+            "version": SAVE_VERSION + 1,
+            "data": { "coins": 10, "unknown_field": true },
+        });
+        let (userdata, version) = migrate(save).unwrap();
+        assert_eq!(version, SAVE_VERSION + 1);
+        assert_eq!(userdata.coins, 10);
+    }
+}
@@ -67,6 +67,17 @@ pub struct AnimZombieArmor2Tag;
 #[derive(Debug, Component)]
 pub struct AnimZombieArmor3Tag;
 
+// 僵尸跳跃动画
+#[derive(Debug, Component)]
+pub struct AnimZombieJumpTag;
+
+// 僵尸游泳动画，叠加在移动动画之上
+#[derive(Debug, Component)]
+pub struct AnimZombieSwimTag;
+
+#[derive(Debug, Component, Default)]
+pub(crate) struct AnimZombieSwimPlayingTag;
+
 impl ZombieBundle {
     pub fn new(zombie: Arc<ZombieInfo>) -> Self {
         Self {
@@ -106,4 +117,22 @@ impl ZombieHp {
         }
         self.hp -= damage;
     }
+
+    // 从上方造成伤害，跳过前置护盾
+    pub fn damage_ignore_shield(&mut self, mut damage: f32, hp: &Hp) {
+        for (armor_hp, armor) in self.armor_hp.iter_mut().zip(&hp.armor) {
+            if armor.shield {
+                continue;
+            }
+            if *armor_hp >= damage {
+                *armor_hp -= damage;
+                damage = 0.0;
+                break;
+            } else {
+                damage -= *armor_hp;
+                *armor_hp = 0.0;
+            }
+        }
+        self.hp -= damage;
+    }
 }
@@ -28,6 +28,9 @@ impl Plugin for ModZombiePlugin {
                 systems::start_armor_1_anim,
                 systems::start_armor_2_anim,
                 systems::start_armor_3_anim,
+                systems::start_jump_anim,
+                systems::start_swim_anim,
+                systems::stop_swim_anim,
             ),
         );
     }
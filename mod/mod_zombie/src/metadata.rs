@@ -2,9 +2,9 @@ use std::{ops::Deref, sync::Arc};
 
 use bevy::{prelude::*, utils::HashMap};
 use bevy_spine::SkeletonData;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ZombieType {
     // 普通僵尸
     Basic,
@@ -92,6 +92,21 @@ pub struct ZombieInfo {
     pub summon_on: SummonOn,
     #[serde(rename = "Jump")]
     pub jump: Option<Jump>,
+    // 飞行僵尸，只能被追踪投掷物命中
+    #[serde(rename = "Flying")]
+    #[serde(default = "default_false")]
+    pub flying: bool,
+    // 碰撞箱
+    #[serde(rename = "Hitbox")]
+    #[serde(default)]
+    pub hitbox: Hitbox,
+    // 啃食距离
+    #[serde(rename = "BiteRange")]
+    #[serde(default = "default_bite_range")]
+    pub bite_range: f32,
+    // 可以在水路中游泳
+    #[serde(rename = "Swim")]
+    pub swim: Option<Swim>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -109,6 +124,9 @@ pub struct Hp {
 pub struct Armor {
     #[serde(default)]
     pub iron: bool,
+    // 前置护盾（如铁栅门），无法抵挡从上方落下的投掷物
+    #[serde(default)]
+    pub shield: bool,
     #[serde(rename = "$value")]
     pub hp: f32,
 }
@@ -136,6 +154,20 @@ pub struct SummonOn {
     pub roof: bool,
 }
 
+// 相对于僵尸位置的偏移
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Hitbox {
+    #[serde(rename = "Left")]
+    pub left: f32,
+    #[serde(rename = "Right")]
+    pub right: f32,
+    #[serde(rename = "Bottom")]
+    pub bottom: f32,
+    #[serde(rename = "Top")]
+    pub top: f32,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Jump {
     // 可以跳跃的次数
@@ -150,6 +182,17 @@ pub struct Jump {
     pub speed: Option<f32>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct Swim {
+    // 水中的移动速度
+    #[serde(rename = "Speed")]
+    pub speed: f32,
+    // 游泳时隐藏的下半身插槽
+    #[serde(rename = "LowerBody")]
+    #[serde(default)]
+    pub lower_body: Vec<String>,
+}
+
 const fn default_true() -> bool {
     true
 }
@@ -158,6 +201,10 @@ const fn default_false() -> bool {
     false
 }
 
+const fn default_bite_range() -> f32 {
+    40.
+}
+
 impl Deref for ZombieRegistry {
     type Target = HashMap<ZombieType, Arc<ZombieInfo>>;
 
@@ -175,3 +222,14 @@ impl Default for SummonOn {
         }
     }
 }
+
+impl Default for Hitbox {
+    fn default() -> Self {
+        Self {
+            left: -31.,
+            right: 15.,
+            bottom: -14.,
+            top: 131.,
+        }
+    }
+}
@@ -2,16 +2,18 @@ use bevy::prelude::*;
 use consts::anim::{
     INDEX_ZOMBIE_ARMOR_1, INDEX_ZOMBIE_ARMOR_2, INDEX_ZOMBIE_ARMOR_3, INDEX_ZOMBIE_CRITICAL,
     INDEX_ZOMBIE_EAT, INDEX_ZOMBIE_EAT_STOP, INDEX_ZOMBIE_FULL_DAMAGE, INDEX_ZOMBIE_HALF_DAMAGE,
-    INDEX_ZOMBIE_MOVE, NAME_ZOMBIE_ARMOR_1, NAME_ZOMBIE_ARMOR_2, NAME_ZOMBIE_ARMOR_3,
-    NAME_ZOMBIE_CRITICAL, NAME_ZOMBIE_EAT, NAME_ZOMBIE_EAT_STOP, NAME_ZOMBIE_FULL_DAMAGE,
-    NAME_ZOMBIE_HALF_DAMAGE, NAME_ZOMBIE_MOVE,
+    INDEX_ZOMBIE_JUMP, INDEX_ZOMBIE_MOVE, INDEX_ZOMBIE_SWIM, NAME_ZOMBIE_ARMOR_1,
+    NAME_ZOMBIE_ARMOR_2, NAME_ZOMBIE_ARMOR_3, NAME_ZOMBIE_CRITICAL, NAME_ZOMBIE_EAT,
+    NAME_ZOMBIE_EAT_STOP, NAME_ZOMBIE_FULL_DAMAGE, NAME_ZOMBIE_HALF_DAMAGE, NAME_ZOMBIE_JUMP,
+    NAME_ZOMBIE_MOVE, NAME_ZOMBIE_SWIM,
 };
 use fw_actor::{looping_anim, oneshot_anim};
 
 use crate::components::{
     AnimZombieArmor1Tag, AnimZombieArmor2Tag, AnimZombieArmor3Tag, AnimZombieCriticalTag,
     AnimZombieEatPlayingTag, AnimZombieEatStopTag, AnimZombieEatTag, AnimZombieFullDamageTag,
-    AnimZombieHalfDamageTag, AnimZombieMovePlayingTag, AnimZombieMoveTag,
+    AnimZombieHalfDamageTag, AnimZombieJumpTag, AnimZombieMovePlayingTag, AnimZombieMoveTag,
+    AnimZombieSwimPlayingTag, AnimZombieSwimTag,
 };
 
 looping_anim!(
@@ -80,3 +82,19 @@ oneshot_anim!(
     INDEX_ZOMBIE_ARMOR_3,
     NAME_ZOMBIE_ARMOR_3
 );
+
+oneshot_anim!(
+    AnimZombieJumpTag,
+    start_jump_anim,
+    INDEX_ZOMBIE_JUMP,
+    NAME_ZOMBIE_JUMP
+);
+
+looping_anim!(
+    AnimZombieSwimTag,
+    AnimZombieSwimPlayingTag,
+    start_swim_anim,
+    stop_swim_anim,
+    INDEX_ZOMBIE_SWIM,
+    NAME_ZOMBIE_SWIM
+);
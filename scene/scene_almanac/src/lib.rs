@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use resource::{AlmanacPage, AlmanacSelection};
+use scene_base::GameScene;
+
+mod resource;
+mod setup;
+mod tag;
+mod update;
+
+pub struct SceneAlmanacPlugin;
+
+impl Plugin for SceneAlmanacPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AlmanacPage>()
+            .init_resource::<AlmanacSelection>()
+            .add_systems(
+                OnEnter(GameScene::Almanac),
+                (
+                    setup::setup_bgm,
+                    setup::setup_camera,
+                    setup::setup_background,
+                    setup::setup_page_buttons,
+                    setup::setup_back_button,
+                    setup::reset_page,
+                ),
+            )
+            .add_systems(OnExit(GameScene::Almanac), setup::clear_scene)
+            .add_systems(
+                Update,
+                (
+                    update::click_plant_page_button,
+                    update::click_zombie_page_button,
+                    update::click_back_button,
+                    (
+                        update::update_grid.run_if(resource_changed::<AlmanacPage>),
+                        update::input_select_entry,
+                        update::update_preview.run_if(resource_changed::<AlmanacSelection>),
+                    )
+                        .chain(),
+                )
+                    .run_if(in_state(GameScene::Almanac)),
+            );
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::tag::AlmanacEntry;
+
+// 当前显示植物还是僵尸
+#[derive(Debug, Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AlmanacPage {
+    #[default]
+    Plant,
+    Zombie,
+}
+
+// 当前选中的条目
+#[derive(Debug, Resource, Default)]
+pub(crate) struct AlmanacSelection(pub Option<AlmanacEntry>);
@@ -0,0 +1,146 @@
+use bevy::{prelude::*, render::camera::ScalingMode, sprite::Anchor};
+use fw_button::components::{ButtonBackground, ButtonBundle, ButtonHotspot};
+use fw_ftxm::{FtxmSource, MainMusicTable};
+
+use crate::{
+    resource::{AlmanacPage, AlmanacSelection},
+    tag::{BackButtonTag, PlantPageButtonTag, SceneTag, ZombiePageButtonTag},
+};
+
+pub(crate) fn setup_bgm(mut commands: Commands) {
+    commands.spawn((
+        FtxmSource {
+            pot: MainMusicTable::ChooseYourSeeds.into(),
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                far: 1000.,
+                near: -1000.,
+                scaling_mode: ScalingMode::Fixed {
+                    width: 800.,
+                    height: 600.,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_background(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let image = asset_server.load("images/AwardScreen_Back.jpg");
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+fn spawn_small_button(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    font: &Handle<Font>,
+    text: &str,
+    translation: Vec3,
+    tag: impl Component,
+) {
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -56.0, y: -13.0 },
+                    max: Vec2 { x: 56.0, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            tag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: text.to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            color: Color::BLACK,
+                            font_size: 14.0,
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn setup_page_buttons(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    spawn_small_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "植物",
+        Vec3::new(-310.0, 270.0, 10.0),
+        PlantPageButtonTag,
+    );
+    spawn_small_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "僵尸",
+        Vec3::new(-190.0, 270.0, 10.0),
+        ZombiePageButtonTag,
+    );
+}
+
+pub(crate) fn setup_back_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    spawn_small_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "主菜单",
+        Vec3::new(330.0, 270.0, 10.0),
+        BackButtonTag,
+    );
+}
+
+// 每次打开图鉴都从植物页开始
+pub(crate) fn reset_page(mut page: ResMut<AlmanacPage>, mut selection: ResMut<AlmanacSelection>) {
+    *page = AlmanacPage::Plant;
+    selection.0 = None;
+}
+
+pub(crate) fn clear_scene(mut commands: Commands, targets: Query<Entity, With<SceneTag>>) {
+    for entity in &targets {
+        commands.entity(entity).despawn_recursive();
+    }
+}
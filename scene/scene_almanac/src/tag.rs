@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use mod_plant::metadata::PlantType;
+use mod_zombie::metadata::ZombieType;
+
+#[derive(Debug, Component)]
+pub(crate) struct SceneTag;
+
+// 图鉴条目，同时作为列表中卡片的组件
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AlmanacEntry {
+    Plant(PlantType),
+    Zombie(ZombieType),
+}
+
+// 当前页的卡片列表
+#[derive(Debug, Component)]
+pub(crate) struct GridTag;
+
+// 选中条目的预览、名称与属性
+#[derive(Debug, Component)]
+pub(crate) struct PreviewTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct PlantPageButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct ZombiePageButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct BackButtonTag;
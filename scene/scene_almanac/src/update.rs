@@ -0,0 +1,353 @@
+use bevy::{prelude::*, sprite::Anchor, text::Text2dBounds};
+use bevy_spine::SpineBundle;
+use fw_actor::components::AnimStandbyTag;
+use fw_button::components::ButtonInteraction;
+use fw_cursor::CursorPosition;
+use mod_plant::{
+    components::PlantSeedBundle,
+    metadata::{PlantInfo, PlantRegistry},
+};
+use mod_userdata::UserData;
+use mod_zombie::metadata::{ZombieInfo, ZombieRegistry};
+use scene_base::GameScene;
+
+use crate::{
+    resource::{AlmanacPage, AlmanacSelection},
+    tag::{
+        AlmanacEntry, BackButtonTag, GridTag, PlantPageButtonTag, PreviewTag, SceneTag,
+        ZombiePageButtonTag,
+    },
+};
+
+// 卡片列表的左上角、间距与每行的数量
+const GRID_ORIGIN: Vec2 = Vec2 {
+    x: -345.0,
+    y: 195.0,
+};
+const GRID_SPACING: Vec2 = Vec2 { x: 55.0, y: 75.0 };
+const GRID_COLUMNS: usize = 7;
+// 卡片的一半尺寸，用于点击判定
+const CARD_HALF_SIZE: Vec2 = Vec2 { x: 25.0, y: 35.0 };
+// 预览的位置
+const PREVIEW_X: f32 = 200.0;
+
+pub(crate) fn click_plant_page_button(
+    button: Query<&ButtonInteraction, With<PlantPageButtonTag>>,
+    mut page: ResMut<AlmanacPage>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    page.set_if_neq(AlmanacPage::Plant);
+}
+
+pub(crate) fn click_zombie_page_button(
+    button: Query<&ButtonInteraction, With<ZombiePageButtonTag>>,
+    mut page: ResMut<AlmanacPage>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    page.set_if_neq(AlmanacPage::Zombie);
+}
+
+pub(crate) fn click_back_button(
+    button: Query<&ButtonInteraction, With<BackButtonTag>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+    next_screen.set(GameScene::Title);
+}
+
+// 切换页面时重新生成卡片列表，并选中第一项
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_grid(
+    mut commands: Commands,
+    page: Res<AlmanacPage>,
+    mut selection: ResMut<AlmanacSelection>,
+    grid: Query<Entity, With<GridTag>>,
+    plant_registry: Res<PlantRegistry>,
+    zombie_registry: Res<ZombieRegistry>,
+    userdata: Res<UserData>,
+    asset_server: Res<AssetServer>,
+) {
+    for entity in &grid {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // 只显示已解锁的植物和遇到过的僵尸
+    let entries: Vec<_> = match *page {
+        AlmanacPage::Plant => {
+            let mut plants: Vec<_> = plant_registry
+                .keys()
+                .filter(|plant| userdata.unlock_plugins.contains(*plant))
+                .copied()
+                .collect();
+            plants.sort();
+            plants.into_iter().map(AlmanacEntry::Plant).collect()
+        }
+        AlmanacPage::Zombie => {
+            let mut zombies: Vec<_> = zombie_registry
+                .keys()
+                .filter(|zombie| userdata.seen_zombies.contains(*zombie))
+                .copied()
+                .collect();
+            zombies.sort_by_key(|zombie| *zombie as u32);
+            zombies.into_iter().map(AlmanacEntry::Zombie).collect()
+        }
+    };
+
+    let font = asset_server.load("font/fzcgbk.ttf");
+    if entries.is_empty() {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    match *page {
+                        AlmanacPage::Plant => "还没有解锁植物",
+                        AlmanacPage::Zombie => "还没有遇到过僵尸",
+                    },
+                    TextStyle {
+                        font,
+                        color: Color::srgb(0.2, 0.5, 0.8),
+                        ..Default::default()
+                    },
+                ),
+                text_anchor: Anchor::TopLeft,
+                transform: Transform::from_xyz(GRID_ORIGIN.x - 25.0, GRID_ORIGIN.y + 35.0, 1.0),
+                ..Default::default()
+            },
+            GridTag,
+            SceneTag,
+        ));
+    }
+
+    let seed_background = asset_server.load("images/SeedPacket_Larger.png");
+    for (i, entry) in entries.iter().enumerate() {
+        let translation = Vec3 {
+            x: GRID_ORIGIN.x + GRID_SPACING.x * (i % GRID_COLUMNS) as f32,
+            y: GRID_ORIGIN.y - GRID_SPACING.y * (i / GRID_COLUMNS) as f32,
+            z: 1.0,
+        };
+
+        match entry {
+            AlmanacEntry::Plant(plant) => {
+                let Some(plant_info) = plant_registry.get(plant) else {
+                    continue;
+                };
+
+                let mut plant_bundle = PlantSeedBundle::new(plant_info.clone());
+                plant_bundle.transform = Transform::from_translation(translation);
+                commands.spawn((plant_bundle, *entry, GridTag, SceneTag));
+            }
+            AlmanacEntry::Zombie(zombie) => {
+                let Some(zombie_info) = zombie_registry.get(zombie) else {
+                    continue;
+                };
+
+                commands
+                    .spawn((
+                        SpatialBundle::from_transform(Transform::from_translation(translation)),
+                        *entry,
+                        GridTag,
+                        SceneTag,
+                    ))
+                    .with_children(|parent| {
+                        // 种子包背景
+                        parent.spawn(SpriteBundle {
+                            texture: seed_background.clone(),
+                            transform: Transform::from_scale(Vec3 {
+                                x: 0.5,
+                                y: 0.5,
+                                z: 1.0,
+                            }),
+                            ..Default::default()
+                        });
+
+                        // 僵尸
+                        parent.spawn(SpineBundle {
+                            skeleton: zombie_info.render.spine_skeleton.clone(),
+                            transform: Transform::from_xyz(0.0, -20.0, 0.01)
+                                .with_scale(Vec3::ONE * 0.2),
+                            ..Default::default()
+                        });
+                    });
+            }
+        }
+    }
+
+    selection.0 = entries.first().copied();
+}
+
+pub(crate) fn input_select_entry(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    cursor_position: Res<CursorPosition>,
+    cards: Query<(&AlmanacEntry, &GlobalTransform)>,
+    mut selection: ResMut<AlmanacSelection>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some((entry, _)) = cards.iter().find(|(_, transform)| {
+        let offset = cursor_position.world_position - transform.translation().truncate();
+        offset.x.abs() <= CARD_HALF_SIZE.x && offset.y.abs() <= CARD_HALF_SIZE.y
+    }) else {
+        return;
+    };
+
+    if selection.0 != Some(*entry) {
+        selection.0 = Some(*entry);
+    }
+}
+
+fn plant_stats(plant_info: &PlantInfo) -> String {
+    let mut stats = format!(
+        "阳光：{}\n冷却：{}秒\n生命值：{}",
+        plant_info.sunshine, plant_info.cooldown, plant_info.hp
+    );
+    if let Some(shoot) = &plant_info.shoot {
+        stats.push_str(&format!("\n攻击间隔：{}秒", shoot.shoot_cooldown));
+    }
+    stats
+}
+
+fn zombie_stats(zombie_info: &ZombieInfo) -> String {
+    let armor: f32 = zombie_info.hp.armor.iter().map(|armor| armor.hp).sum();
+    format!(
+        "生命值：{}\n护甲：{}\n速度：{}",
+        zombie_info.hp.real + zombie_info.hp.critical,
+        armor,
+        zombie_info.speed
+    )
+}
+
+// 显示选中条目的动画与属性，并放大对应的卡片
+pub(crate) fn update_preview(
+    mut commands: Commands,
+    selection: Res<AlmanacSelection>,
+    previews: Query<Entity, With<PreviewTag>>,
+    mut cards: Query<(&AlmanacEntry, &mut Transform)>,
+    plant_registry: Res<PlantRegistry>,
+    zombie_registry: Res<ZombieRegistry>,
+    asset_server: Res<AssetServer>,
+) {
+    for entity in &previews {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    for (entry, mut transform) in &mut cards {
+        transform.scale = match selection.0 == Some(*entry) {
+            true => Vec3::ONE * 1.15,
+            false => Vec3::ONE,
+        };
+    }
+
+    let (skeleton, scale, name, stats, description) = match selection.0 {
+        Some(AlmanacEntry::Plant(plant)) => {
+            let Some(plant_info) = plant_registry.get(&plant) else {
+                return;
+            };
+            (
+                plant_info.render.spine_skeleton.clone(),
+                1.5,
+                plant_info.name.clone(),
+                plant_stats(plant_info),
+                plant_info.description.clone(),
+            )
+        }
+        Some(AlmanacEntry::Zombie(zombie)) => {
+            let Some(zombie_info) = zombie_registry.get(&zombie) else {
+                return;
+            };
+            (
+                zombie_info.render.spine_skeleton.clone(),
+                1.0,
+                zombie_info.name.clone(),
+                zombie_stats(zombie_info),
+                zombie_info.description.clone(),
+            )
+        }
+        None => return,
+    };
+
+    commands.spawn((
+        SpineBundle {
+            skeleton,
+            transform: Transform::from_xyz(PREVIEW_X, 60.0, 1.0).with_scale(Vec3::ONE * scale),
+            ..Default::default()
+        },
+        AnimStandbyTag,
+        PreviewTag,
+        SceneTag,
+    ));
+
+    let font = asset_server.load("font/fzcgbk.ttf");
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                name,
+                TextStyle {
+                    font: font.clone(),
+                    color: Color::srgb(0.8, 0.5, 0.2),
+                    ..Default::default()
+                },
+            ),
+            transform: Transform::from_xyz(PREVIEW_X, -20.0, 1.0),
+            ..Default::default()
+        },
+        PreviewTag,
+        SceneTag,
+    ));
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                stats,
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 16.0,
+                    color: Color::BLACK,
+                },
+            ),
+            text_anchor: Anchor::TopCenter,
+            transform: Transform::from_xyz(PREVIEW_X, -45.0, 1.0),
+            ..Default::default()
+        },
+        PreviewTag,
+        SceneTag,
+    ));
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                description,
+                TextStyle {
+                    font,
+                    font_size: 16.0,
+                    color: Color::srgb(0.2, 0.5, 0.8),
+                },
+            ),
+            text_anchor: Anchor::TopCenter,
+            text_2d_bounds: Text2dBounds {
+                size: Vec2 { x: 340.0, y: 120.0 },
+            },
+            transform: Transform::from_xyz(PREVIEW_X, -140.0, 1.0),
+            ..Default::default()
+        },
+        PreviewTag,
+        SceneTag,
+    ));
+}
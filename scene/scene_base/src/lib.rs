@@ -8,6 +8,14 @@ pub enum GameScene {
     Title,
     Game,
     Reward,
+    // 对战模式大厅
+    Lobby,
+    // 图鉴
+    Almanac,
+    // 商店
+    Shop,
+    // 冒险模式选关
+    LevelSelect,
 }
 
 pub struct SceneBasePlugin;
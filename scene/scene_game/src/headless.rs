@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use bevy::{
+    core::FrameCount,
+    input::InputSystem,
+    prelude::*,
+    time::{TimeSystem, TimeUpdateStrategy},
+};
+use fw_cursor::CursorPosition;
+use mod_level::{CurrentLevel, LevelRegistry, LevelType};
+use mod_plant::metadata::PlantType;
+use mod_userdata::UserData;
+use scene_base::GameScene;
+
+use crate::{
+    replay::{self, Replay, ReplayPlayer, ReplayRecorder},
+    resource::GameRng,
+    tag::RewardTag,
+    GameState,
+};
+
+// 无窗口模式，不创建窗口、音频设备与渲染管线，用于数值平衡与回归测试
+// 需要在 ModLevelPlugin 之后添加，且不能与 ModUserdataPlugin 同时使用，避免覆盖存档
+pub struct SceneGameHeadlessPlugin {
+    // 随机数种子，相同的种子与输入得到相同的结果
+    pub seed: u64,
+    // 关卡
+    pub level: LevelType,
+    // 携带的植物，数量即为植物槽数量，因此不会进入选卡界面
+    pub plants: Vec<PlantType>,
+    // 是否有铲子
+    pub shovel: bool,
+    // 每帧的时长，与真实时间无关
+    pub timestep: Duration,
+    // 超过该帧数仍未结束则视为超时
+    pub max_frames: u32,
+    // 回放的录像，关卡、植物与随机数种子以录像为准
+    pub replay: Option<Replay>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadlessOutcome {
+    Win,
+    Lose,
+    Timeout,
+}
+
+// 对局结果
+#[derive(Debug, Resource, Default, PartialEq, Eq)]
+pub struct HeadlessResult {
+    pub outcome: Option<HeadlessOutcome>,
+    pub frames: u32,
+}
+
+#[derive(Resource)]
+struct HeadlessConfig {
+    max_frames: u32,
+}
+
+impl Default for SceneGameHeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            level: LevelType::Adventure { level: 1 },
+            plants: vec![PlantType::PeaShooter],
+            shovel: false,
+            timestep: Duration::from_secs_f64(1.0 / 60.0),
+            max_frames: 60 * 60 * 30,
+            replay: None,
+        }
+    }
+}
+
+impl Plugin for SceneGameHeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let (level, plants, shovel) = match &self.replay {
+            Some(replay) => (replay.level, replay.plants.clone(), replay.shovel),
+            None => (self.level, self.plants.clone(), self.shovel),
+        };
+        let level = app
+            .world()
+            .resource::<LevelRegistry>()
+            .get(&level)
+            .unwrap()
+            .clone();
+
+        if let Some(replay) = &self.replay {
+            app.insert_resource(ReplayPlayer::new(replay.clone(), self.timestep));
+        }
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(self.timestep))
+            .insert_resource(GameRng::with_seed(self.seed))
+            .insert_resource(CurrentLevel(level))
+            .insert_resource(UserData {
+                unlock_plugins: plants.iter().copied().collect(),
+                plant_solt_count: plants.len(),
+                unlock_shovel: shovel,
+                ..Default::default()
+            })
+            .insert_resource(HeadlessConfig {
+                max_frames: self.max_frames,
+            })
+            // 回放时同样会录制，但不写入文件
+            .insert_resource(ReplayRecorder {
+                save: false,
+                ..Default::default()
+            })
+            .init_resource::<CursorPosition>()
+            .init_resource::<HeadlessResult>()
+            .add_systems(Startup, enter_game_scene)
+            .add_systems(
+                First,
+                (
+                    replay::update_replay_time.before(TimeSystem),
+                    replay::update_replay_cursor,
+                )
+                    .run_if(resource_exists::<ReplayPlayer>),
+            )
+            .add_systems(
+                PreUpdate,
+                replay::update_replay_input
+                    .after(InputSystem)
+                    .run_if(resource_exists::<ReplayPlayer>),
+            )
+            .add_systems(OnEnter(GameState::Fail), check_fail)
+            .add_systems(
+                Last,
+                (despawn_audio, check_reward, check_timeout, exit_on_finish).chain(),
+            );
+    }
+}
+
+fn enter_game_scene(mut state: ResMut<NextState<GameScene>>) {
+    state.set(GameScene::Game);
+}
+
+fn check_fail(frame: Res<FrameCount>, mut result: ResMut<HeadlessResult>) {
+    if result.outcome.is_some() {
+        return;
+    }
+
+    result.outcome = Some(HeadlessOutcome::Lose);
+    result.frames = frame.0;
+}
+
+// 没有音频设备，音效实体不会自动移除
+fn despawn_audio(mut commands: Commands, audios: Query<Entity, With<Handle<AudioSource>>>) {
+    for entity in &audios {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// 最后一波僵尸被消灭后出现奖励，视为胜利，无需拾取
+fn check_reward(
+    rewards: Query<(), With<RewardTag>>,
+    frame: Res<FrameCount>,
+    mut result: ResMut<HeadlessResult>,
+) {
+    if result.outcome.is_some() || rewards.is_empty() {
+        return;
+    }
+
+    result.outcome = Some(HeadlessOutcome::Win);
+    result.frames = frame.0;
+}
+
+fn check_timeout(
+    config: Res<HeadlessConfig>,
+    frame: Res<FrameCount>,
+    mut result: ResMut<HeadlessResult>,
+) {
+    if result.outcome.is_some() || frame.0 < config.max_frames {
+        return;
+    }
+
+    result.outcome = Some(HeadlessOutcome::Timeout);
+    result.frames = frame.0;
+}
+
+fn exit_on_finish(result: Res<HeadlessResult>, mut exit: EventWriter<AppExit>) {
+    if result.outcome.is_some() {
+        exit.send(AppExit::Success);
+    }
+}
@@ -1,15 +1,22 @@
-use bevy::prelude::*;
-use bevy_spatial::*;
+use bevy::{prelude::*, time::TimeSystem, window::WindowFocused};
 use fw_transition::{OnReenter, OnReexit};
-use resource::{Sunshine, ZombieWaveController};
+use mod_net::{NetSession, NetSystem, VersusCommand};
+use replay::{PlayerAction, ReplayRecorder};
+use resource::{Brains, GameRng, GameSpeed, Sunshine, VersusCommands, ZombieWaveController};
 use scene_base::GameScene;
-use tag::{PlantTag, ZombieAttackableTag};
+use spatial::LaneIndex;
+use tag::{PlantTag, VersusTarget, ZombieAttackableTag};
 
+pub mod headless;
+mod net;
 mod particle;
+pub mod replay;
 mod resource;
 mod setup;
+mod spatial;
 mod tag;
 mod update;
+mod versus;
 
 pub struct SceneGamePlugin;
 
@@ -22,6 +29,7 @@ enum GameState {
     Main,       // 开始对局
     Exit,       // 结算
     Fail,       // 游戏结束
+    Paused,     // 暂停
 }
 
 impl Plugin for SceneGamePlugin {
@@ -29,15 +37,34 @@ impl Plugin for SceneGamePlugin {
         app.init_state::<GameState>()
             .init_resource::<Sunshine>()
             .init_resource::<ZombieWaveController>()
-            .add_plugins(
-                AutomaticUpdate::<PlantTag>::new()
-                    .with_spatial_ds(SpatialStructure::KDTree2)
-                    .with_transform(TransformMode::GlobalTransform),
+            .init_resource::<GameRng>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<Brains>()
+            .init_resource::<LaneIndex<PlantTag>>()
+            .init_resource::<LaneIndex<ZombieAttackableTag>>()
+            .init_resource::<LaneIndex<VersusTarget>>()
+            .init_resource::<ReplayRecorder>()
+            .init_resource::<VersusCommands>()
+            .add_event::<PlayerAction>()
+            .add_event::<VersusCommand>()
+            // 无窗口模式下没有 WindowPlugin，需要手动注册
+            .add_event::<WindowFocused>()
+            .add_systems(
+                PreUpdate,
+                (
+                    spatial::update_lane_index::<PlantTag>,
+                    spatial::update_lane_index::<ZombieAttackableTag>,
+                    spatial::update_lane_index::<VersusTarget>,
+                )
+                    .run_if(in_state(GameScene::Game)),
             )
-            .add_plugins(
-                AutomaticUpdate::<ZombieAttackableTag>::new()
-                    .with_spatial_ds(SpatialStructure::KDTree2)
-                    .with_transform(TransformMode::GlobalTransform),
+            // 联机时按帧同步的进度决定本帧的时间
+            .add_systems(
+                First,
+                net::update_lockstep_time
+                    .after(NetSystem)
+                    .before(TimeSystem)
+                    .run_if(in_state(GameScene::Game)),
             )
             .add_systems(
                 OnReenter(GameScene::Game),
@@ -52,6 +79,7 @@ impl Plugin for SceneGamePlugin {
                     setup::setup_cleanup_car,
                     setup::setup_standby_zombie,
                     setup::setup_resources,
+                    versus::setup_versus,
                     setup::setup_sunshine_solt,
                     setup::setup_init_state,
                     setup::setup_init_bgm,
@@ -68,12 +96,27 @@ impl Plugin for SceneGamePlugin {
                 OnEnter(GameState::Enter),
                 (setup::setup_enter_timer, setup::insert_seed_pickable_tag),
             )
+            // 从暂停恢复时同样会进入 Main，只在开局时执行
+            // 生存模式每轮都会从 Enter 进入 Main，部分逻辑只在第一轮执行
             .add_systems(
-                OnEnter(GameState::Main),
+                OnTransition {
+                    exited: GameState::Enter,
+                    entered: GameState::Main,
+                },
                 (
                     setup::setup_game_bgm,
-                    setup::setup_game_ui,
-                    setup::clear_standby_zombie,
+                    (
+                        setup::setup_game_ui,
+                        setup::clear_standby_zombie,
+                        (
+                            replay::start_replay,
+                            setup::setup_zombie_waves,
+                            setup::setup_graves,
+                        )
+                            .chain(),
+                        net::start_lockstep,
+                    )
+                        .run_if(update::predicate_first_round),
                 )
                     .run_if(in_state(GameScene::Game)),
             )
@@ -84,6 +127,8 @@ impl Plugin for SceneGamePlugin {
                     setup::stop_standby_anims,
                     setup::stop_zombie_anims,
                     setup::setup_gameover_timer,
+                    replay::save_replay,
+                    net::stop_lockstep,
                 )
                     .run_if(in_state(GameScene::Game)),
             )
@@ -93,10 +138,46 @@ impl Plugin for SceneGamePlugin {
                     setup::setup_exit_timer,
                     setup::setup_exit_reward_anim,
                     setup::setup_exit_overlay,
+                    replay::save_replay,
+                    net::stop_lockstep,
                 )
                     .run_if(in_state(GameScene::Game)),
             )
-            .add_systems(OnReexit(GameScene::Game), setup::clear_scene)
+            .add_systems(
+                OnEnter(GameState::Paused),
+                (
+                    setup::pause_game,
+                    fw_ftxm::pause_playback,
+                    setup::setup_pause_menu,
+                ),
+            )
+            .add_systems(
+                OnExit(GameState::Paused),
+                (
+                    setup::resume_game,
+                    fw_ftxm::resume_playback,
+                    setup::clear_pause_menu,
+                ),
+            )
+            .add_systems(
+                OnReexit(GameScene::Game),
+                (
+                    setup::clear_scene,
+                    setup::reset_game_speed,
+                    replay::save_replay,
+                    net::clear_session,
+                ),
+            )
+            .add_systems(
+                Last,
+                (
+                    (replay::record_replay, replay::save_replay_on_exit).chain(),
+                    net::finish_tick
+                        .run_if(in_state(GameScene::Game).and_then(resource_exists::<NetSession>)),
+                ),
+            )
+            // 回放与联机要求每次运行的系统顺序一致，随机数的消耗顺序与状态的修改顺序才能一致
+            // 因此对局中的系统全部按顺序执行
             .add_systems(
                 Update,
                 (
@@ -108,7 +189,16 @@ impl Plugin for SceneGamePlugin {
                         update::update_image_cut,
                         update::update_material_alpha,
                         update::update_follow_camera,
-                    ),
+                        update::record_seen_zombies,
+                        (
+                            update::input_toggle_pause,
+                            (update::click_speed_button, update::update_game_speed).chain(),
+                        )
+                            .chain()
+                            .run_if(not(resource_exists::<NetSession>)),
+                        net::check_net_error,
+                    )
+                        .chain(),
                     // 选卡逻辑
                     (
                         update::update_start_button_enabled,
@@ -116,52 +206,125 @@ impl Plugin for SceneGamePlugin {
                         update::input_giveup_seed,
                         update::start_game_button,
                     )
+                        .chain()
                         .run_if(in_state(GameState::ChooseSeed)),
+                    // 暂停菜单
+                    (
+                        update::click_resume_button,
+                        update::click_restart_button,
+                        update::click_main_menu_button,
+                    )
+                        .chain()
+                        .run_if(in_state(GameState::Paused).or_else(net::predicate_net_failed)),
                     // 游戏主逻辑
                     (
                         // 输入逻辑
                         (
                             // 左键点击事件
+                            // 联机时只能操作本方，且不能使用铲子
                             (
-                                update::plant_seed,
-                                update::collect_sunshine,
-                                update::input_pick_seed,
+                                update::shovel_plant.run_if(not(resource_exists::<NetSession>)),
+                                update::plant_seed.run_if(net::predicate_plant_side),
+                                update::collect_sunshine.run_if(net::predicate_plant_side),
+                                update::collect_coin,
+                                update::input_pick_shovel
+                                    .run_if(not(resource_exists::<NetSession>)),
+                                update::input_pick_seed.run_if(net::predicate_plant_side),
                                 update::input_pick_reward,
+                                (versus::place_zombie_seed, versus::input_pick_zombie_seed)
+                                    .chain()
+                                    .run_if(update::predicate_versus)
+                                    .run_if(net::predicate_zombie_side),
                             )
                                 .chain(),
                             update::cancel_pick_seed,
                             update::update_follow_cursor,
                             update::check_plant_seed_usable,
-                        ),
+                            update::update_shovel_hover,
+                            update::update_shovel_bank,
+                            versus::input_plant_keyboard
+                                .run_if(update::predicate_versus)
+                                .run_if(net::predicate_plant_side),
+                            versus::input_zombie_keyboard
+                                .run_if(update::predicate_versus)
+                                .run_if(net::predicate_zombie_side),
+                        )
+                            .chain(),
+                        // 执行对战操作，联机时双方在同一帧执行
+                        (
+                            net::queue_versus_commands,
+                            versus::apply_sunshine_commands,
+                            versus::apply_plant_commands,
+                            versus::apply_zombie_commands,
+                        )
+                            .chain(),
                         // 植物逻辑
                         (
+                            (
+                                update::sleep_nocturnal_plants,
+                                update::start_wake_up,
+                                update::update_wake_up,
+                                update::start_consume_grave,
+                                update::update_consume_grave,
+                            )
+                                .chain(),
                             update::update_plant_shoot_enable,
                             update::plant_shoot,
                             update::plant_product,
                             update::update_projectile_show,
+                            update::update_throw_projectile_land,
+                            update::update_follow_projectile,
                             update::update_plant_die,
                             update::update_zombie_eat,
                             update::update_plant_instant_timer,
                             update::apply_effect_explode,
+                            update::apply_effect_freeze,
+                            (update::apply_effect_crater, update::update_crater).chain(),
                             particle::apply_cherry_bomb_particle,
                             update::update_plant_hp_anim,
                             update::provide_conveyor_belt_plant,
-                        ),
+                        )
+                            .chain()
+                            .run_if(net::predicate_step),
                         // 僵尸逻辑
                         (
                             update::update_summon_zombie,
                             update::check_zombie_eat_start,
                             update::check_zombie_eat_end,
+                            (
+                                update::update_zombie_jump,
+                                update::cancel_critical_zombie_jump,
+                            )
+                                .chain(),
+                            (
+                                update::update_zombie_swim,
+                                update::update_critical_zombie_swim,
+                            )
+                                .chain(),
                             update::zombie_projectile_damage,
+                            update::update_zombie_status,
+                            update::update_zombie_status_anim,
+                            update::reset_critical_zombie_anim,
                             update::update_zombie_hp_anim,
                             update::update_zombie_enter_critical,
                             update::update_zombie_critical,
                             update::update_zombie_die,
                             update::update_zombie_eat_timer,
-                        ),
+                            update::drop_zombie_coin.run_if(not(update::predicate_versus)),
+                        )
+                            .chain()
+                            .run_if(net::predicate_step),
                         // 流程控制
                         (
-                            (update::update_zombie_wave, update::check_summon_reward).chain(),
+                            (
+                                update::update_zombie_wave,
+                                update::check_summon_reward,
+                                update::check_survival_round,
+                                update::spawn_wave_graves,
+                                update::summon_grave_zombies,
+                            )
+                                .chain()
+                                .run_if(not(update::predicate_versus)),
                             update::update_level_progress,
                             update::update_level_progress_head,
                             update::update_level_progress_flag,
@@ -172,8 +335,25 @@ impl Plugin for SceneGamePlugin {
                             update::remove_outrange_car,
                             update::check_game_over,
                             update::update_reward_solt,
-                        ),
+                        )
+                            .chain()
+                            .run_if(net::predicate_step),
+                        // 对战模式
+                        (
+                            (
+                                versus::update_brains,
+                                versus::update_zombie_seed_card,
+                                versus::versus_target_damage,
+                            )
+                                .chain()
+                                .run_if(net::predicate_step),
+                            versus::update_brains_text,
+                            versus::update_select_marker,
+                        )
+                            .chain()
+                            .run_if(update::predicate_versus),
                     )
+                        .chain()
                         .run_if(in_state(GameState::Main)),
                     // 通用逻辑
                     // 放在这里，以便捡起奖励后，各种物理事件不会停滞
@@ -194,10 +374,14 @@ impl Plugin for SceneGamePlugin {
                             update::bowling_plant_hit,
                             update::bowling_change_velocity,
                         )
+                            .chain()
                             .run_if(update::predicate_bowling),
                     )
-                        .run_if(in_state(GameState::Main).or_else(in_state(GameState::Exit))),
+                        .chain()
+                        .run_if(in_state(GameState::Main).or_else(in_state(GameState::Exit)))
+                        .run_if(net::predicate_step),
                 )
+                    .chain()
                     .run_if(in_state(GameScene::Game)),
             );
     }
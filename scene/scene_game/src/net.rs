@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    sprite::{Anchor, MaterialMesh2dBundle},
+    text::Text2dBounds,
+    time::TimeUpdateStrategy,
+};
+use fw_button::components::ButtonEnabled;
+use mod_net::{hash_state, NetRole, NetSession, VersusCommand, TICK};
+use mod_plant::components::PlantHp;
+use mod_zombie::components::{ZombieHp, ZombieMetadata};
+
+use crate::{
+    resource::{Brains, Sunshine, VersusCommands},
+    setup::spawn_pause_button,
+    tag::{FollowCameraTag, MainMenuButtonTag, NetErrorTag, PlantTag, SceneTag, VersusTarget},
+};
+
+// 开局时开始帧同步
+pub(crate) fn start_lockstep(session: Option<ResMut<NetSession>>) {
+    let Some(mut session) = session else {
+        return;
+    };
+
+    session.start_lockstep();
+}
+
+// 对局结束，双方在同一帧结束，之后不再同步
+pub(crate) fn stop_lockstep(
+    session: Option<ResMut<NetSession>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+
+    session.lockstep.active = false;
+    *strategy = TimeUpdateStrategy::Automatic;
+}
+
+// 离开游戏场景时断开连接
+pub(crate) fn clear_session(
+    mut commands: Commands,
+    session: Option<Res<NetSession>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if session.is_none() {
+        return;
+    }
+
+    commands.remove_resource::<NetSession>();
+    *strategy = TimeUpdateStrategy::Automatic;
+}
+
+// 双方的操作都到达后才推进一帧，否则本帧时间不流逝
+// 需要在 TimeSystem 之前执行
+pub(crate) fn update_lockstep_time(
+    session: Option<ResMut<NetSession>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+    if !session.lockstep.active {
+        return;
+    }
+
+    // 出错后恢复真实时间，以便显示提示
+    if session.error().is_some() {
+        *strategy = TimeUpdateStrategy::Automatic;
+        return;
+    }
+
+    let duration = match session.update_lockstep() {
+        true => TICK,
+        false => Duration::ZERO,
+    };
+    *strategy = TimeUpdateStrategy::ManualDuration(duration);
+}
+
+// 收集本帧的对战操作，本地对战直接执行，联机时延迟到双方的同一帧执行
+pub(crate) fn queue_versus_commands(
+    session: Option<ResMut<NetSession>>,
+    mut events: EventReader<VersusCommand>,
+    mut versus_commands: ResMut<VersusCommands>,
+) {
+    versus_commands.0.clear();
+
+    let Some(mut session) = session.filter(|session| session.lockstep.active) else {
+        versus_commands.0.extend(events.read().cloned());
+        return;
+    };
+
+    session.lockstep.pending.extend(events.read().cloned());
+    if !session.lockstep.stepping {
+        return;
+    }
+
+    session.send_pending();
+    versus_commands.0 = session.take_commands();
+}
+
+// 计算本帧结束时的状态哈希，与对方比较以检查是否同步
+#[allow(clippy::type_complexity)]
+pub(crate) fn finish_tick(
+    session: Option<ResMut<NetSession>>,
+    plants: Query<(&PlantTag, &PlantHp, &GlobalTransform)>,
+    zombies: Query<(&ZombieMetadata, &ZombieHp, &GlobalTransform)>,
+    targets: Query<(&VersusTarget, &GlobalTransform)>,
+    sunshine: Res<Sunshine>,
+    brains: Res<Brains>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+    if !session.lockstep.active || !session.lockstep.stepping {
+        return;
+    }
+
+    // 实体的遍历顺序不影响结果
+    let mut entities = Vec::new();
+    for (PlantTag { metadata, .. }, PlantHp(hp), transform) in &plants {
+        entities.push(hash_state((
+            metadata.id,
+            hp.to_bits(),
+            transform.translation().to_array().map(f32::to_bits),
+        )));
+    }
+    for (ZombieMetadata(metadata), ZombieHp { hp, armor_hp }, transform) in &zombies {
+        entities.push(hash_state((
+            metadata.id,
+            hp.to_bits(),
+            armor_hp.iter().map(|hp| hp.to_bits()).collect::<Vec<_>>(),
+            transform.translation().to_array().map(f32::to_bits),
+        )));
+    }
+    for (VersusTarget { hp }, transform) in &targets {
+        entities.push(hash_state((
+            hp.to_bits(),
+            transform.translation().to_array().map(f32::to_bits),
+        )));
+    }
+    entities.sort_unstable();
+
+    session.finish_tick(hash_state((entities, sunshine.0, brains.count)));
+}
+
+// 联机出错时停止对局，显示原因与返回主菜单的按钮
+pub(crate) fn check_net_error(
+    mut commands: Commands,
+    session: Option<Res<NetSession>>,
+    error_tag: Query<(), With<NetErrorTag>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    camera: Query<&GlobalTransform, With<Camera>>,
+) {
+    let Some(session) = session else {
+        return;
+    };
+    let Some(err) = session.error() else {
+        return;
+    };
+    if !session.lockstep.active || !error_tag.is_empty() {
+        return;
+    }
+
+    let font: Handle<Font> = asset_server.load("font/fzcgbk.ttf");
+    let translation = camera
+        .iter()
+        .next()
+        .map(GlobalTransform::translation)
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            FollowCameraTag,
+            Transform::from_translation(translation),
+            GlobalTransform::default(),
+            ViewVisibility::default(),
+            Visibility::default(),
+            InheritedVisibility::default(),
+            SceneTag,
+            NetErrorTag,
+        ))
+        .with_children(|parent| {
+            // 遮罩
+            parent.spawn(MaterialMesh2dBundle {
+                mesh: meshes.add(Rectangle::default()).into(),
+                material: materials.add(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                transform: Transform::from_xyz(0.0, 0.0, 80.0).with_scale(Vec3::ONE * 1000.),
+                ..Default::default()
+            });
+
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: err.to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            color: Color::srgb(0.83, 0.67, 0.07),
+                        },
+                    }],
+                    justify: JustifyText::Center,
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2 { x: 600.0, y: 200.0 },
+                },
+                transform: Transform::from_xyz(0.0, 60.0, 80.1),
+                ..Default::default()
+            });
+
+            spawn_pause_button(
+                parent,
+                &asset_server,
+                &font,
+                "主菜单",
+                -60.0,
+                ButtonEnabled::Enabled,
+                MainMenuButtonTag,
+            );
+        });
+}
+
+// 本帧是否推进模拟，本地游戏总是推进
+pub(crate) fn predicate_step(session: Option<Res<NetSession>>) -> bool {
+    session.map_or(true, |session| {
+        !session.lockstep.active || session.lockstep.stepping
+    })
+}
+
+// 联机出错，等待返回主菜单
+pub(crate) fn predicate_net_failed(session: Option<Res<NetSession>>) -> bool {
+    session.is_some_and(|session| session.lockstep.active && session.error().is_some())
+}
+
+// 是否由本地操作植物方，联机时主机为植物方
+pub(crate) fn predicate_plant_side(session: Option<Res<NetSession>>) -> bool {
+    session.map_or(true, |session| session.role == NetRole::Host)
+}
+
+// 是否由本地操作僵尸方
+pub(crate) fn predicate_zombie_side(session: Option<Res<NetSession>>) -> bool {
+    session.map_or(true, |session| session.role == NetRole::Join)
+}
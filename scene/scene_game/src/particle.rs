@@ -2,7 +2,7 @@ use std::{any::TypeId, ops::Range, time::Duration};
 
 use bevy::prelude::*;
 use fw_anim::{AnimationBundle, AnimationClip, AnimationClips, CustomAnimationTrigger, KeyFrame};
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::tag::{CherryBombParticleTag, ColorAlphaFade, SceneTag, ToDespawn};
 
@@ -16,6 +16,9 @@ pub(crate) fn apply_cherry_bomb_particle(
         return;
     }
 
+    // 粒子只是表现，不占用对局随机数
+    let mut rng = rand::thread_rng();
+
     let cloud = asset_server.load("particles/ExplosionCloud.png");
     let powie = asset_server.load("particles/ExplosionPowie.png");
 
@@ -26,6 +29,7 @@ pub(crate) fn apply_cherry_bomb_particle(
         for _ in 0..10 {
             gen_particle(
                 &mut commands,
+                &mut rng,
                 cloud.clone(),
                 translation,
                 0.5,
@@ -37,6 +41,7 @@ pub(crate) fn apply_cherry_bomb_particle(
         for _ in 0..10 {
             gen_particle(
                 &mut commands,
+                &mut rng,
                 cloud.clone(),
                 translation,
                 1.5,
@@ -63,14 +68,13 @@ pub(crate) fn apply_cherry_bomb_particle(
 
 fn gen_particle(
     commands: &mut Commands,
+    rng: &mut impl Rng,
     texture: Handle<Image>,
     translation: Vec3,
     scale: f32,
     color: Color,
     range: Range<f32>,
 ) {
-    let mut rng = thread_rng();
-
     let start_transform = Transform::from_scale(Vec3::ONE * scale).with_translation(Vec3 {
         x: translation.x,
         y: translation.y,
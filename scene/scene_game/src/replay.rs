@@ -0,0 +1,384 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use fw_cursor::CursorPosition;
+use mod_level::{CurrentLevel, LevelType};
+use mod_net::NetSession;
+use mod_plant::{
+    components::{PlantMetaData, PlantSeed},
+    metadata::PlantType,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    resource::{GameRng, GameSpeed},
+    tag::{PickableSeed, SeedbankTag, ShovelBankTag},
+};
+
+// 录像格式版本，修改 Replay 结构时需要递增
+pub const REPLAY_VERSION: u32 = 1;
+
+// 最多保留的录像数量，超出时删除最早的录像
+const MAX_REPLAYS: usize = 20;
+
+// 对局录像，帧数从进入 Main 状态开始计算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    // 进入 Main 状态时重新设置的随机数种子
+    pub seed: u64,
+    pub level: LevelType,
+    // 植物槽中的植物
+    pub plants: Vec<PlantType>,
+    // 是否有铲子
+    #[serde(default)]
+    pub shovel: bool,
+    // 每一帧的时长（纳秒），第 i 项为第 i + 1 帧
+    pub frame_times: Vec<u64>,
+    pub actions: Vec<ReplayAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayAction {
+    pub frame: u32,
+    pub action: PlayerAction,
+}
+
+// 玩家操作，坐标均为世界坐标
+#[derive(Debug, Clone, Event, Serialize, Deserialize)]
+pub enum PlayerAction {
+    // 拿起种子，坐标为种子卡的位置
+    PickSeed { plant: PlantType, x: f32, y: f32 },
+    // 放下拿起的种子
+    CancelSeed,
+    // 种植，坐标为格子的位置
+    Plant { x: f32, y: f32 },
+    // 收集阳光，坐标为阳光的位置
+    CollectSunshine { x: f32, y: f32 },
+    // 暂停或继续游戏，回放时按下 Esc
+    TogglePause,
+    // 切换游戏速度
+    SetSpeed { speed: f32 },
+    // 捡起或放回铲子，坐标为铲子栏的位置
+    PickShovel { x: f32, y: f32 },
+    // 使用铲子，坐标为格子的位置
+    Shovel { x: f32, y: f32 },
+}
+
+// 录制中的录像
+#[derive(Resource)]
+pub(crate) struct ReplayRecorder {
+    pub replay: Option<Replay>,
+    pub frame: u32,
+    // 对局结束后是否写入文件
+    pub save: bool,
+}
+
+// 回放录像，只在无窗口模式下使用
+#[derive(Resource)]
+pub(crate) struct ReplayPlayer {
+    pub replay: Replay,
+    // 录像结束后每帧的时长
+    pub timestep: Duration,
+    next_action: usize,
+    buttons: Vec<MouseButton>,
+    keys: Vec<KeyCode>,
+}
+
+impl Default for ReplayRecorder {
+    fn default() -> Self {
+        Self {
+            replay: None,
+            frame: 0,
+            save: true,
+        }
+    }
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay, timestep: Duration) -> Self {
+        Self {
+            replay,
+            timestep,
+            next_action: 0,
+            buttons: Vec::new(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+pub fn load_replay(path: &Path) -> Option<Replay> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            warn!("failed to open replay {}: {err}", path.display());
+            return None;
+        }
+    };
+
+    let replay = match serde_json::from_reader::<_, Replay>(file) {
+        Ok(replay) => replay,
+        Err(err) => {
+            warn!("failed to read replay {}: {err}", path.display());
+            return None;
+        }
+    };
+
+    if replay.version != REPLAY_VERSION {
+        warn!(
+            "replay version {} is not supported, expected {REPLAY_VERSION}",
+            replay.version
+        );
+        return None;
+    }
+
+    Some(replay)
+}
+
+// 进入 Main 状态，重新设置随机数种子并开始录制
+// 联机时使用主机的种子，双方的随机数保持一致
+pub(crate) fn start_replay(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut rng: ResMut<GameRng>,
+    player: Option<Res<ReplayPlayer>>,
+    session: Option<Res<NetSession>>,
+    current_level: Res<CurrentLevel>,
+    seeds: Query<(&PlantMetaData, &GlobalTransform), (With<PlantSeed>, With<SeedbankTag>)>,
+    shovel_bank: Query<(), With<ShovelBankTag>>,
+) {
+    let seed = match (&player, &session) {
+        (Some(player), _) => player.replay.seed,
+        (None, Some(session)) => session.seed,
+        (None, None) => rng.gen(),
+    };
+    *rng = GameRng::with_seed(seed);
+
+    // 生存模式每轮结束后重新选卡，录像中没有选卡操作，无法回放，因此不录制
+    // 对战中键盘操作与放置僵尸通过 VersusCommand 执行，录像中没有这些操作，同样不录制
+    if current_level.survival.is_some() || current_level.versus.is_some() {
+        recorder.replay = None;
+        return;
+    }
+
+    let mut plants = seeds.iter().collect::<Vec<_>>();
+    plants.sort_by(|(_, a), (_, b)| a.translation().x.total_cmp(&b.translation().x));
+
+    recorder.replay = Some(Replay {
+        version: REPLAY_VERSION,
+        seed,
+        level: current_level.id,
+        plants: plants
+            .into_iter()
+            .map(|(PlantMetaData(metadata), _)| metadata.id)
+            .collect(),
+        shovel: !shovel_bank.is_empty(),
+        frame_times: Vec::new(),
+        actions: Vec::new(),
+    });
+    recorder.frame = 0;
+}
+
+// 记录本帧的时长与玩家操作
+pub(crate) fn record_replay(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut actions: EventReader<PlayerAction>,
+    time: Res<Time<Real>>,
+) {
+    let recorder = &mut *recorder;
+    let Some(replay) = &mut recorder.replay else {
+        actions.clear();
+        return;
+    };
+
+    // 进入 Main 状态的那一帧已经更新过时间，从下一帧开始记录
+    if recorder.frame > 0 {
+        replay.frame_times.push(time.delta().as_nanos() as u64);
+    }
+    // 回放时无法在第 0 帧输入，记录到下一帧
+    for action in actions.read() {
+        replay.actions.push(ReplayAction {
+            frame: recorder.frame.max(1),
+            action: action.clone(),
+        });
+    }
+
+    recorder.frame += 1;
+}
+
+// 停止录制并写入文件
+pub(crate) fn save_replay(mut recorder: ResMut<ReplayRecorder>) {
+    stop_recording(&mut recorder);
+}
+
+// 关闭窗口时保存未结束的录像
+pub(crate) fn save_replay_on_exit(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut exit: EventReader<AppExit>,
+) {
+    if exit.read().next().is_none() {
+        return;
+    }
+
+    stop_recording(&mut recorder);
+}
+
+fn stop_recording(recorder: &mut ReplayRecorder) {
+    let Some(replay) = recorder.replay.take() else {
+        return;
+    };
+    if !recorder.save {
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path = replay_dir().join(format!("replay-{timestamp}.json"));
+    let result = fs::create_dir_all(replay_dir())
+        .and_then(|_| File::create(&path))
+        .and_then(|file| serde_json::to_writer(file, &replay).map_err(Into::into));
+
+    match result {
+        Ok(_) => info!("replay saved to {}", path.display()),
+        Err(err) => error!("failed to write {}: {err}", path.display()),
+    }
+
+    remove_old_replays();
+}
+
+// 只保留最新的 MAX_REPLAYS 个录像
+fn remove_old_replays() {
+    let entries = match fs::read_dir(replay_dir()) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("failed to read {}: {err}", replay_dir().display());
+            return;
+        }
+    };
+
+    let mut replays = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let timestamp = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("replay-")?
+                .strip_suffix(".json")?
+                .parse::<u64>()
+                .ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    replays.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    for (_, path) in replays.into_iter().skip(MAX_REPLAYS) {
+        if let Err(err) = fs::remove_file(&path) {
+            warn!("failed to remove {}: {err}", path.display());
+        }
+    }
+}
+
+// 回放：按录像设置每帧时长，需要在 TimeSystem 之前执行
+pub(crate) fn update_replay_time(
+    recorder: Res<ReplayRecorder>,
+    player: Res<ReplayPlayer>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    let frame_time = recorder
+        .replay
+        .as_ref()
+        .and_then(|_| recorder.frame.checked_sub(1))
+        .and_then(|index| player.replay.frame_times.get(index as usize))
+        .map(|nanos| Duration::from_nanos(*nanos))
+        .unwrap_or(player.timestep);
+
+    *strategy = TimeUpdateStrategy::ManualDuration(frame_time);
+}
+
+// 回放：将本帧的操作转换为光标位置与按键
+pub(crate) fn update_replay_cursor(
+    recorder: Res<ReplayRecorder>,
+    mut player: ResMut<ReplayPlayer>,
+    mut cursor_position: ResMut<CursorPosition>,
+    mut game_speed: ResMut<GameSpeed>,
+    seeds: Query<(&PlantMetaData, &GlobalTransform), (With<PlantSeed>, With<PickableSeed>)>,
+) {
+    if recorder.replay.is_none() {
+        return;
+    }
+
+    let player = &mut *player;
+    while let Some(ReplayAction { frame, action }) = player.replay.actions.get(player.next_action) {
+        if *frame > recorder.frame {
+            break;
+        }
+        player.next_action += 1;
+
+        let position = match action {
+            // 种子位置可能与录制时不同，选择同类植物中最近的种子卡
+            PlayerAction::PickSeed { plant, x, y } => {
+                let target = Vec2 { x: *x, y: *y };
+                seeds
+                    .iter()
+                    .filter(|(PlantMetaData(metadata), _)| metadata.id == *plant)
+                    .map(|(_, transform)| transform.translation().truncate())
+                    .min_by(|a, b| {
+                        a.distance_squared(target)
+                            .total_cmp(&b.distance_squared(target))
+                    })
+            }
+            PlayerAction::CancelSeed => {
+                player.buttons.push(MouseButton::Right);
+                continue;
+            }
+            PlayerAction::Plant { x, y }
+            | PlayerAction::CollectSunshine { x, y }
+            | PlayerAction::PickShovel { x, y }
+            | PlayerAction::Shovel { x, y } => Some(Vec2 { x: *x, y: *y }),
+            PlayerAction::TogglePause => {
+                player.keys.push(KeyCode::Escape);
+                continue;
+            }
+            PlayerAction::SetSpeed { speed } => {
+                game_speed.0 = *speed;
+                continue;
+            }
+        };
+
+        if let Some(position) = position {
+            cursor_position.world_position = position;
+        }
+        player.buttons.push(MouseButton::Left);
+        // 每帧只能点击一个位置，同一帧的其余操作顺延到之后的帧
+        break;
+    }
+}
+
+// 回放：按下本帧的按键，需要在 InputSystem 清空点击事件之后执行
+pub(crate) fn update_replay_input(
+    mut player: ResMut<ReplayPlayer>,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    mouse_button_input.release_all();
+    for button in player.buttons.drain(..) {
+        mouse_button_input.press(button);
+    }
+    keyboard_input.release_all();
+    for key in player.keys.drain(..) {
+        keyboard_input.press(key);
+    }
+}
+
+fn replay_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pvz")
+        .join("replays")
+}
@@ -1,11 +1,128 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
+use mod_level::{Level, Wave};
+use mod_net::VersusCommand;
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
 
 #[derive(Resource, Default)]
 pub(crate) struct Sunshine(pub u32);
 
+// 对战模式中僵尸方的脑子，按固定间隔增加
+#[derive(Resource, Default)]
+pub(crate) struct Brains {
+    pub count: u32,
+    pub timer: Timer,
+}
+
+// 本帧需要执行的对战操作，联机时为双方在这一帧的操作
+#[derive(Resource, Default)]
+pub(crate) struct VersusCommands(pub Vec<VersusCommand>);
+
 #[derive(Resource, Default)]
 pub(crate) struct ZombieWaveController {
     pub next_wave_timer: Timer,
     pub next_wave_index: usize,
     pub trigger_huge_wave: bool,
+    // 本轮的僵尸，生存模式下每轮重新生成
+    pub waves: Vec<Wave>,
+    // 生存模式的轮数（已完成的旗数）
+    pub round: u32,
+    // 最后一轮结束后出现奖励
+    pub final_round: bool,
+    // 本波需要新生成的墓碑数量
+    pub pending_graves: u32,
+    // 最后一波时从所有墓碑中生成僵尸
+    pub summon_from_graves: bool,
+}
+
+impl ZombieWaveController {
+    // 开始新的一轮，生成的僵尸会用到随机数
+    pub fn start_round(&mut self, level: &Level, round: u32, rng: &mut impl Rng) {
+        self.waves = match &level.generated_waves {
+            Some(generated) => {
+                let seed = match generated.seed {
+                    Some(seed) => seed.wrapping_add(round as u64),
+                    None => rng.gen(),
+                };
+                generated.generate(seed, round * generated.count)
+            }
+            None => level.waves.clone(),
+        };
+        self.final_round = match &level.survival {
+            Some(survival) => !survival.endless && round + 1 >= survival.flags,
+            None => true,
+        };
+        self.round = round;
+        self.next_wave_timer = Timer::new(
+            Duration::from_secs_f32(level.first_wave_time),
+            TimerMode::Once,
+        );
+        self.next_wave_index = 0;
+        self.trigger_huge_wave = false;
+        self.pending_graves = 0;
+        self.summon_from_graves = false;
+    }
+}
+
+// 可选的游戏速度，点击速度按钮依次切换
+pub(crate) const GAME_SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+
+// 游戏速度，作用于虚拟时间与音乐
+#[derive(Resource)]
+pub(crate) struct GameSpeed(pub f32);
+
+impl Default for GameSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl GameSpeed {
+    // 下一档速度
+    pub fn next(&self) -> f32 {
+        GAME_SPEEDS
+            .iter()
+            .copied()
+            .find(|speed| *speed > self.0)
+            .unwrap_or(GAME_SPEEDS[0])
+    }
+
+    pub fn label(&self) -> String {
+        format!("速度 {}x", self.0)
+    }
+}
+
+// 对局中使用的随机数，使用相同的种子可以复现对局
+#[derive(Resource)]
+pub(crate) struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn with_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
 }
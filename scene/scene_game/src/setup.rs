@@ -8,7 +8,7 @@ use bevy::{
 };
 use fw_actor::components::AnimStandbyTag;
 use fw_anim::{AnimationBundle, AnimationClip, AnimationClips, CustomAnimationTrigger, KeyFrame};
-use fw_button::components::{ButtonBackground, ButtonBundle, ButtonHotspot};
+use fw_button::components::{ButtonBackground, ButtonBundle, ButtonEnabled, ButtonHotspot};
 use fw_ftxm::{FtxmSource, MainMusicTable};
 use mod_level::{CurrentLevel, LevelBackground, Music, SodType, SpecialRule, Zombie};
 use mod_plant::{
@@ -20,20 +20,42 @@ use mod_zombie::{
     components::{AnimZombieEatTag, AnimZombieMoveTag, ZombieBundle},
     metadata::ZombieRegistry,
 };
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::{
-    resource::{Sunshine, ZombieWaveController},
+    resource::{GameRng, GameSpeed, Sunshine, ZombieWaveController},
     tag::{
         ChooseableSeedTag, CleanerCar, ConveyorBeltAnimTag, ConveyorBeltSolt, ConveyorBeltTag,
-        FollowCameraTag, GameTimer, GameTimerTag, GameUiTag, LanePosition, MaterialColorAnim,
-        NaturalSunshineSolt, PickableSeed, PlantSolt, RewardSolt, RewardTag, SceneTag,
-        SeedChooserTag, SeedTransformInChooserBox, SeedbankTag, ShowLevelProgressShiftLeft,
-        SoltType, StandbyZombieTag, StartGameButtonTag, SunshineText, ZombieSolt, ZombieTag,
+        FollowCameraTag, GameTimer, GameTimerTag, GameUiTag, LanePosition, MainMenuButtonTag,
+        MaterialColorAnim, NaturalSunshineSolt, OptionsButtonTag, PauseMenuTag, PickableSeed,
+        PlantSolt, RestartButtonTag, ResumeButtonTag, RewardSolt, RewardTag, SceneTag,
+        SeedChooserTag, SeedTransformInChooserBox, SeedbankTag, ShovelBankTag, ShovelTag,
+        ShowLevelProgressShiftLeft, SoltType, SpeedButtonTag, SpeedTextTag, StandbyZombieTag,
+        StartGameButtonTag, SunshineText, ZombieSolt, ZombieTag,
     },
+    update::place_graves,
     GameState,
 };
 
+// 泳池场景的六路，中间两路为水路
+const POOL_LANES: [SoltType; 6] = [
+    SoltType::Dirt,
+    SoltType::Dirt,
+    SoltType::River,
+    SoltType::River,
+    SoltType::Dirt,
+    SoltType::Dirt,
+];
+
+// 每一路中心的纵坐标，泳池场景有六路，每路较窄
+pub(crate) fn lane_y(background: &LevelBackground, lane: u8) -> f32 {
+    if background.is_pool() {
+        lane as f32 * 85.0 - 85.0 * 2.5 - 20.0
+    } else {
+        lane as f32 * 100.0 - 110.0 * 2.0
+    }
+}
+
 pub(crate) fn setup_camera(mut commands: Commands) {
     commands.spawn((
         Camera2dBundle {
@@ -145,14 +167,14 @@ pub(crate) fn setup_background(
             ));
         }
     }
-
-    // TODO: 如果是泳池，需要处理河道
 }
 
 pub(crate) fn setup_plant_solt(mut commands: Commands, current_level: Res<CurrentLevel>) {
     // 坚果保龄球模式中，只能种在左侧三列
-    let count_each_lane = match current_level.special_rule {
-        Some(SpecialRule::Bowling) => 3,
+    // 对战模式中，右侧几列由僵尸方放置僵尸
+    let count_each_lane = match (&current_level.special_rule, &current_level.versus) {
+        (Some(SpecialRule::Bowling), _) => 3,
+        (_, Some(versus)) => versus.plant_columns,
         _ => 9,
     };
 
@@ -207,27 +229,16 @@ pub(crate) fn setup_plant_solt(mut commands: Commands, current_level: Res<Curren
             }
         }
         LevelBackground::Swim | LevelBackground::SwimFog => {
-            for (lane, solt_type) in [
-                SoltType::Dirt,
-                SoltType::Dirt,
-                SoltType::River,
-                SoltType::River,
-                SoltType::Dirt,
-                SoltType::Dirt,
-            ]
-            .into_iter()
-            .enumerate()
-            {
+            for (lane, solt_type) in POOL_LANES.into_iter().enumerate() {
                 for i in 0..count_each_lane {
                     commands.spawn((
                         PlantSolt {
                             solt_type,
                             ..Default::default()
                         },
-                        // TODO: 调整坐标
                         Transform::from_xyz(
                             -320.0 + i as f32 * 80.0,
-                            lane as f32 * 100.0 - 110.0 * 2.0,
+                            lane_y(&current_level.background, lane as u8),
                             10.0,
                         ),
                         GlobalTransform::default(),
@@ -311,21 +322,14 @@ pub(crate) fn setup_zombie_solt(mut commands: Commands, current_level: Res<Curre
             }
         }
         LevelBackground::Swim | LevelBackground::SwimFog => {
-            for (lane, solt_type) in [
-                SoltType::Dirt,
-                SoltType::Dirt,
-                SoltType::River,
-                SoltType::River,
-                SoltType::Dirt,
-                SoltType::Dirt,
-            ]
-            .into_iter()
-            .enumerate()
-            {
+            for (lane, solt_type) in POOL_LANES.into_iter().enumerate() {
                 commands.spawn((
                     ZombieSolt { solt_type },
-                    // TODO: 调整坐标
-                    Transform::from_xyz(500., lane as f32 * 100.0 - 110.0 * 2.0 - 15.0, 10.0),
+                    Transform::from_xyz(
+                        500.,
+                        lane_y(&current_level.background, lane as u8) - 15.0,
+                        10.0,
+                    ),
                     GlobalTransform::default(),
                     SceneTag,
                     LanePosition {
@@ -359,8 +363,12 @@ pub(crate) fn setup_seedbank(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     userdata: Res<UserData>,
+    current_level: Res<CurrentLevel>,
 ) {
     let seedbank = asset_server.load("images/seedbank.png");
+    let solt_count = current_level
+        .preset_plants()
+        .map_or(userdata.plant_solt_count, <[_]>::len);
 
     commands
         .spawn((
@@ -380,7 +388,7 @@ pub(crate) fn setup_seedbank(
                         sprite: Sprite {
                             anchor: Anchor::TopLeft,
                             custom_size: Some(Vec2 {
-                                x: 80.0 + 12.0 + 5.0 + 55.0 * userdata.plant_solt_count as f32,
+                                x: 80.0 + 12.0 + 5.0 + 55.0 * solt_count as f32,
                                 y: 87.0,
                             }),
                             ..Default::default()
@@ -425,6 +433,39 @@ pub(crate) fn setup_seedbank(
                         },
                         SunshineText,
                     ));
+
+                    // 铲子栏，位于种子栏右侧
+                    if !userdata.unlock_shovel {
+                        return;
+                    }
+                    parent
+                        .spawn((
+                            SpriteBundle {
+                                sprite: Sprite {
+                                    anchor: Anchor::TopLeft,
+                                    ..Default::default()
+                                },
+                                texture: asset_server.load("images/ShovelBank.png"),
+                                transform: Transform::from_xyz(
+                                    80.0 + 12.0 + 5.0 + 55.0 * solt_count as f32,
+                                    0.0,
+                                    0.01,
+                                ),
+                                ..Default::default()
+                            },
+                            ShovelBankTag,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                SpriteBundle {
+                                    texture: asset_server.load("images/Shovel.png"),
+                                    transform: Transform::from_xyz(35.0, -36.0, 0.01)
+                                        .with_scale(Vec3::ONE * 0.75),
+                                    ..Default::default()
+                                },
+                                ShovelTag,
+                            ));
+                        });
                 });
         });
 }
@@ -523,10 +564,20 @@ pub(crate) fn setup_cleanup_car(
     };
 
     for lane in car_lane {
+        // 水路中使用泳池清理车
+        // TODO: 暂无泳池清理车的贴图，先用染色的割草机代替
+        let pool = current_level.background.is_pool()
+            && matches!(POOL_LANES[lane as usize], SoltType::River);
+        let color = if pool {
+            Color::srgb(0.6, 0.85, 1.0)
+        } else {
+            Color::WHITE
+        };
         commands.spawn((
             SpriteBundle {
                 sprite: Sprite {
                     anchor: Anchor::Center,
+                    color,
                     ..Default::default()
                 },
                 visibility: Visibility::Hidden,
@@ -546,12 +597,37 @@ pub(crate) fn setup_resources(
     mut zombie_wave_controller: ResMut<ZombieWaveController>,
 ) {
     sunshine.0 = current_level.sunshine;
-    zombie_wave_controller.next_wave_timer = Timer::new(
-        Duration::from_secs_f32(current_level.first_wave_time),
-        TimerMode::Once,
+    // 僵尸在进入 Main 状态后生成
+    *zombie_wave_controller = ZombieWaveController::default();
+}
+
+// 生成第一轮僵尸，生存模式会用到随机数，需要在重新设置种子之后执行
+pub(crate) fn setup_zombie_waves(
+    current_level: Res<CurrentLevel>,
+    mut zombie_wave_controller: ResMut<ZombieWaveController>,
+    mut rng: ResMut<GameRng>,
+) {
+    zombie_wave_controller.start_round(&current_level, 0, &mut *rng);
+}
+
+// 开局时的墓碑，同样需要在重新设置种子之后执行
+pub(crate) fn setup_graves(
+    mut commands: Commands,
+    current_level: Res<CurrentLevel>,
+    mut solts: Query<(&mut PlantSolt, &GlobalTransform, &LanePosition)>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    place_graves(
+        &mut commands,
+        &mut solts,
+        current_level.graves.count,
+        current_level.graves.min_column,
+        &mut rng,
+        &mut meshes,
+        &mut materials,
     );
-    zombie_wave_controller.next_wave_index = 0;
-    zombie_wave_controller.trigger_huge_wave = false;
 }
 
 pub(crate) fn setup_init_state(mut state: ResMut<NextState<GameState>>) {
@@ -685,16 +761,23 @@ pub(crate) fn setup_init_timer(
     time += 4.0;
 
     // 检查是否需要选卡，满足以下任意条件则无需选卡
-    // 1. 植物小于植物槽数量
-    // 2. 传送带关卡
-    if userdata.unlock_plugins.len() <= userdata.plant_solt_count
-        || current_level.conveyor_belt.is_some()
-    {
-        // 植物数量小于植物槽，无需选卡
-        // 需要在seedbank位置生成对应植物
-        let mut unlock_plugins = userdata.unlock_plugins.iter().collect::<Vec<_>>();
-        unlock_plugins.sort();
-        for (i, plant) in unlock_plugins.into_iter().enumerate() {
+    // 1. 关卡指定了植物
+    // 2. 植物小于植物槽数量
+    // 3. 传送带关卡
+    let preset_plants = match current_level.preset_plants() {
+        Some(plants) => Some(plants.to_vec()),
+        None if userdata.unlock_plugins.len() <= userdata.plant_solt_count
+            || current_level.conveyor_belt.is_some() =>
+        {
+            let mut unlock_plugins = userdata.unlock_plugins.iter().copied().collect::<Vec<_>>();
+            unlock_plugins.sort();
+            Some(unlock_plugins)
+        }
+        None => None,
+    };
+    if let Some(plants) = preset_plants {
+        // 无需选卡，需要在seedbank位置生成对应植物
+        for (i, plant) in plants.iter().enumerate() {
             commands
                 .spawn((
                     FollowCameraTag,
@@ -965,8 +1048,60 @@ pub(crate) fn setup_game_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     current_level: Res<CurrentLevel>,
+    speed: Res<GameSpeed>,
 ) {
     let font: Handle<Font> = asset_server.load("font/fzcgbk.ttf");
+
+    // 游戏速度按钮
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(335.0, 282.0, 50.1),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -55.5, y: -13.0 },
+                    max: Vec2 { x: 55.5, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            SpeedButtonTag,
+            SceneTag,
+            GameUiTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text {
+                        sections: vec![TextSection {
+                            value: speed.label(),
+                            style: TextStyle {
+                                font: font.clone(),
+                                color: Color::srgb(0.83, 0.67, 0.07),
+                                font_size: 16.0,
+                            },
+                        }],
+                        ..Default::default()
+                    },
+                    text_anchor: Anchor::Center,
+                    transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                    ..Default::default()
+                },
+                SpeedTextTag,
+            ));
+        });
+
     commands.spawn((
         Text2dBundle {
             text: Text {
@@ -1019,7 +1154,8 @@ pub(crate) fn setup_standby_zombie(
     current_level: Res<CurrentLevel>,
     zombie_registry: Res<ZombieRegistry>,
 ) {
-    let mut rng = thread_rng();
+    // 预览僵尸只是表现，不占用对局随机数
+    let mut rng = rand::thread_rng();
     for Zombie { zombie, count } in &current_level.preview_zombies {
         let zombie = zombie_registry.get(zombie).unwrap();
 
@@ -1242,3 +1378,179 @@ pub(crate) fn setup_exit_overlay(
         SceneTag,
     ));
 }
+
+// 暂停：Spine 动画、GameTimer 与各类移动均使用虚拟时间，暂停虚拟时间即可全部冻结
+pub(crate) fn pause_game(mut time: ResMut<Time<Virtual>>, audios: Query<&AudioSink>) {
+    time.pause();
+    for audio in &audios {
+        audio.pause();
+    }
+}
+
+// 离开场景时恢复正常速度，下一局与录像均从正常速度开始
+pub(crate) fn reset_game_speed(mut time: ResMut<Time<Virtual>>, mut speed: ResMut<GameSpeed>) {
+    *speed = GameSpeed::default();
+    time.set_relative_speed(speed.0);
+}
+
+// 继续游戏
+pub(crate) fn resume_game(mut time: ResMut<Time<Virtual>>, audios: Query<&AudioSink>) {
+    time.unpause();
+    for audio in &audios {
+        audio.play();
+    }
+}
+
+pub(crate) fn setup_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    camera: Query<&GlobalTransform, With<Camera>>,
+) {
+    let font: Handle<Font> = asset_server.load("font/fzcgbk.ttf");
+    // 直接放到相机位置，避免第一帧错位
+    let translation = camera
+        .iter()
+        .next()
+        .map(GlobalTransform::translation)
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            FollowCameraTag,
+            Transform::from_translation(translation),
+            GlobalTransform::default(),
+            ViewVisibility::default(),
+            Visibility::default(),
+            InheritedVisibility::default(),
+            SceneTag,
+            PauseMenuTag,
+        ))
+        .with_children(|parent| {
+            // 遮罩
+            parent.spawn(MaterialMesh2dBundle {
+                mesh: meshes.add(Rectangle::default()).into(),
+                material: materials.add(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                transform: Transform::from_xyz(0.0, 0.0, 80.0).with_scale(Vec3::ONE * 1000.),
+                ..Default::default()
+            });
+
+            // 标题
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: "游戏暂停".to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            font_size: 40.0,
+                            color: Color::srgb(0.83, 0.67, 0.07),
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 130.0, 80.1),
+                ..Default::default()
+            });
+
+            spawn_pause_button(
+                parent,
+                &asset_server,
+                &font,
+                "继续游戏",
+                60.0,
+                ButtonEnabled::Enabled,
+                ResumeButtonTag,
+            );
+            spawn_pause_button(
+                parent,
+                &asset_server,
+                &font,
+                "重新开始",
+                0.0,
+                ButtonEnabled::Enabled,
+                RestartButtonTag,
+            );
+            // 选项界面尚未实现，与标题界面一致，暂时禁用
+            spawn_pause_button(
+                parent,
+                &asset_server,
+                &font,
+                "选项",
+                -60.0,
+                ButtonEnabled::Disabled,
+                OptionsButtonTag,
+            );
+            spawn_pause_button(
+                parent,
+                &asset_server,
+                &font,
+                "主菜单",
+                -120.0,
+                ButtonEnabled::Enabled,
+                MainMenuButtonTag,
+            );
+        });
+}
+
+pub(crate) fn spawn_pause_button(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    font: &Handle<Font>,
+    text: &str,
+    y: f32,
+    enabled: ButtonEnabled,
+    tag: impl Component,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button.png"),
+                    hover: asset_server.load("images/SeedChooser_Button_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button_Disabled.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(0.0, y, 80.1),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -78.0, y: -21.0 },
+                    max: Vec2 { x: 78.0, y: 21.0 },
+                }]),
+                enabled,
+                ..Default::default()
+            },
+            tag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: text.to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            color: Color::srgb(0.83, 0.67, 0.07),
+                            ..Default::default()
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn clear_pause_menu(mut commands: Commands, menus: Query<Entity, With<PauseMenuTag>>) {
+    for entity in &menus {
+        commands.entity(entity).despawn_recursive();
+    }
+}
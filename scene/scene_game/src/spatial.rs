@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::tag::LanePosition;
+
+// 按路划分、每路按 x 升序排列的实体索引
+#[derive(Resource)]
+pub(crate) struct LaneIndex<T> {
+    lanes: Vec<Vec<(Vec2, Entity)>>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for LaneIndex<T> {
+    fn default() -> Self {
+        Self {
+            lanes: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> LaneIndex<T> {
+    // 某一路的全部实体
+    pub fn lane(&self, lane: u8) -> &[(Vec2, Entity)] {
+        self.lanes
+            .get(lane as usize)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // x 位于 [min_x, max_x] 内的实体，lane 为 None 时查询所有路
+    pub fn range(
+        &self,
+        lane: Option<u8>,
+        min_x: f32,
+        max_x: f32,
+    ) -> impl Iterator<Item = (Vec2, Entity)> + '_ {
+        self.lanes
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| lane.map_or(true, |lane| lane as usize == *index))
+            .flat_map(move |(_, entities)| {
+                let start = entities.partition_point(|(position, _)| position.x < min_x);
+                let end = entities.partition_point(|(position, _)| position.x <= max_x);
+                entities[start..end.max(start)].iter().copied()
+            })
+    }
+
+    // 某一路中位于 x 前方（x 更大）的第一个实体
+    pub fn first_ahead(&self, lane: u8, x: f32) -> Option<(Vec2, Entity)> {
+        let entities = self.lane(lane);
+        entities
+            .get(entities.partition_point(|(position, _)| position.x < x))
+            .copied()
+    }
+
+    // 某一路中位于 x 后方（x 更小）的第一个实体
+    pub fn first_behind(&self, lane: u8, x: f32) -> Option<(Vec2, Entity)> {
+        let entities = self.lane(lane);
+        entities
+            .partition_point(|(position, _)| position.x <= x)
+            .checked_sub(1)
+            .map(|index| entities[index])
+    }
+
+    // 某一路中距离 x 最近的实体
+    pub fn nearest_in_lane(&self, lane: u8, x: f32) -> Option<(Vec2, Entity)> {
+        [self.first_ahead(lane, x), self.first_behind(lane, x)]
+            .into_iter()
+            .flatten()
+            .min_by(|(a, _), (b, _)| (a.x - x).abs().total_cmp(&(b.x - x).abs()))
+    }
+
+    // 所有实体
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, Entity)> + '_ {
+        self.lanes.iter().flatten().copied()
+    }
+}
+
+// 每帧重建索引
+pub(crate) fn update_lane_index<T: Component>(
+    mut index: ResMut<LaneIndex<T>>,
+    entities: Query<(Entity, &LanePosition, &GlobalTransform), With<T>>,
+) {
+    for lane in &mut index.lanes {
+        lane.clear();
+    }
+
+    for (entity, lane_position, transform) in &entities {
+        let lane = lane_position.lane as usize;
+        if index.lanes.len() <= lane {
+            index.lanes.resize_with(lane + 1, Vec::new);
+        }
+
+        let translation = transform.translation();
+        index.lanes[lane].push((
+            Vec2 {
+                x: translation.x,
+                y: translation.y,
+            },
+            entity,
+        ));
+    }
+
+    for lane in &mut index.lanes {
+        lane.sort_unstable_by(|(a, _), (b, _)| a.x.total_cmp(&b.x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw(index)
+    }
+
+    // 每一路的实体按 x 排序，实体编号为在所有实体中的序号
+    fn lane_index(lanes: &[&[f32]]) -> LaneIndex<()> {
+        let mut next = 0;
+        let lanes = lanes
+            .iter()
+            .enumerate()
+            .map(|(lane, xs)| {
+                let mut entities = xs
+                    .iter()
+                    .map(|x| {
+                        next += 1;
+                        (Vec2::new(*x, lane as f32), entity(next - 1))
+                    })
+                    .collect::<Vec<_>>();
+                entities.sort_by(|(a, _), (b, _)| a.x.total_cmp(&b.x));
+                entities
+            })
+            .collect();
+
+        LaneIndex {
+            lanes,
+            marker: PhantomData,
+        }
+    }
+
+    fn entities(iter: impl Iterator<Item = (Vec2, Entity)>) -> Vec<Entity> {
+        iter.map(|(_, entity)| entity).collect()
+    }
+
+    #[test]
+    fn range_includes_both_ends() {
+        let index = lane_index(&[&[0.0, 10.0, 20.0, 30.0]]);
+        assert_eq!(
+            entities(index.range(Some(0), 10.0, 20.0)),
+            [entity(1), entity(2)]
+        );
+        assert_eq!(entities(index.range(Some(0), 10.0, 10.0)), [entity(1)]);
+        assert!(entities(index.range(Some(0), 11.0, 19.0)).is_empty());
+        // 区间颠倒时没有结果
+        assert!(entities(index.range(Some(0), 20.0, 10.0)).is_empty());
+    }
+
+    #[test]
+    fn range_all_lanes() {
+        let index = lane_index(&[&[0.0, 50.0], &[], &[25.0]]);
+        assert_eq!(
+            entities(index.range(None, 0.0, 30.0)),
+            [entity(0), entity(2)]
+        );
+        assert_eq!(entities(index.range(Some(2), 0.0, 30.0)), [entity(2)]);
+    }
+
+    #[test]
+    fn first_ahead_and_behind_include_x() {
+        let index = lane_index(&[&[0.0, 10.0, 20.0]]);
+        assert_eq!(index.first_ahead(0, 10.0).map(|(_, e)| e), Some(entity(1)));
+        assert_eq!(index.first_behind(0, 10.0).map(|(_, e)| e), Some(entity(1)));
+        assert_eq!(index.first_ahead(0, 11.0).map(|(_, e)| e), Some(entity(2)));
+        assert_eq!(index.first_behind(0, 11.0).map(|(_, e)| e), Some(entity(1)));
+        assert_eq!(index.first_ahead(0, 21.0), None);
+        assert_eq!(index.first_behind(0, -1.0), None);
+    }
+
+    #[test]
+    fn empty_and_missing_lanes() {
+        let index = lane_index(&[&[], &[10.0]]);
+        assert!(index.lane(0).is_empty());
+        assert_eq!(index.first_ahead(0, 0.0), None);
+        assert_eq!(index.first_behind(0, 0.0), None);
+        assert_eq!(index.nearest_in_lane(0, 0.0), None);
+
+        // 超出已有的路
+        assert!(index.lane(5).is_empty());
+        assert_eq!(index.first_ahead(5, 0.0), None);
+        assert_eq!(index.first_behind(5, 0.0), None);
+        assert_eq!(index.nearest_in_lane(5, 0.0), None);
+        assert!(entities(index.range(Some(5), f32::MIN, f32::MAX)).is_empty());
+
+        let index = LaneIndex::<()>::default();
+        assert_eq!(index.iter().count(), 0);
+        assert_eq!(index.nearest_in_lane(0, 0.0), None);
+    }
+
+    #[test]
+    fn nearest_in_lane() {
+        let index = lane_index(&[&[0.0, 10.0, 30.0]]);
+        assert_eq!(
+            index.nearest_in_lane(0, 8.0).map(|(_, e)| e),
+            Some(entity(1))
+        );
+        assert_eq!(
+            index.nearest_in_lane(0, 25.0).map(|(_, e)| e),
+            Some(entity(2))
+        );
+        assert_eq!(
+            index.nearest_in_lane(0, -5.0).map(|(_, e)| e),
+            Some(entity(0))
+        );
+        assert_eq!(
+            index.nearest_in_lane(0, 40.0).map(|(_, e)| e),
+            Some(entity(2))
+        );
+        // 距离相同时选择前方的实体
+        assert_eq!(
+            index.nearest_in_lane(0, 20.0).map(|(_, e)| e),
+            Some(entity(2))
+        );
+        assert_eq!(
+            index.nearest_in_lane(0, 5.0).map(|(_, e)| e),
+            Some(entity(1))
+        );
+    }
+}
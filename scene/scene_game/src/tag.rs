@@ -1,7 +1,7 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bevy::{prelude::*, utils::HashSet};
-use mod_plant::metadata::PlantInfo;
+use mod_plant::metadata::{PlantInfo, ProjectileInfo, StatusEffectType};
 use mod_zombie::metadata::ZombieType;
 
 use crate::GameState;
@@ -124,6 +124,69 @@ pub(crate) struct SeedChooserTag;
 #[derive(Component)]
 pub(crate) struct PickableSeed;
 
+// 铲子栏
+#[derive(Component)]
+pub(crate) struct ShovelBankTag;
+
+// 铲子栏中的铲子
+#[derive(Component)]
+pub(crate) struct ShovelTag;
+
+// 捡起的铲子
+#[derive(Component)]
+pub(crate) struct PickShovel;
+
+// 对战模式：僵尸种子卡
+#[derive(Component)]
+pub(crate) struct ZombieSeedCard {
+    pub zombie: ZombieType,
+    // 消耗的脑子
+    pub cost: u32,
+    // 冷却计时，结束后可以使用
+    pub cooldown: Timer,
+}
+
+// 对战模式：僵尸种子卡上的遮罩，无法使用时显示
+#[derive(Component)]
+pub(crate) struct ZombieSeedOverlay;
+
+// 对战模式：捡起的僵尸种子卡
+#[derive(Component)]
+pub(crate) struct PickZombieSeed {
+    pub seed: Entity,
+}
+
+// 对战模式：脑子文本
+#[derive(Component)]
+pub(crate) struct BrainsText;
+
+// 对战模式：僵尸方的目标，被摧毁时植物方获胜
+#[derive(Component)]
+pub(crate) struct VersusTarget {
+    pub hp: f32,
+}
+
+// 对战模式：键盘操作的光标
+#[derive(Component, Default)]
+pub(crate) struct VersusCursor {
+    pub lane: u8,
+    pub column: u8,
+    // 选中的种子卡序号
+    pub selected: usize,
+}
+
+// 对战模式：标记选中的种子卡
+#[derive(Component)]
+pub(crate) struct VersusSelectMarker;
+
+// 对战模式：属于植物方
+#[derive(Component)]
+pub(crate) struct PlantSideTag;
+
+// 对战模式：属于僵尸方
+#[derive(Component)]
+pub(crate) struct ZombieSideTag;
+
 // 阳光文本，将阳光显示到对应位置
 #[derive(Component)]
 pub(crate) struct SunshineText;
@@ -174,6 +237,10 @@ pub(crate) struct PlantSolt {
     pub hole: Option<Entity>,
 }
 
+// 墓碑，挡住普通植物的种植，最后一波时会从中生成僵尸
+#[derive(Component)]
+pub(crate) struct GraveTag;
+
 #[derive(Default, Clone, Copy)]
 pub(crate) enum SoltType {
     #[default]
@@ -202,6 +269,10 @@ pub(crate) struct PlantHpAnim {
 #[derive(Component)]
 pub(crate) struct ShowLevelProgressShiftLeft;
 
+// 关卡进度条的所有部分，生存模式下每轮重新显示
+#[derive(Component)]
+pub(crate) struct LevelProgressTag;
+
 // 关卡进度条，进度部分，通过裁剪图片实现进度展示
 #[derive(Component)]
 pub(crate) struct LevelProgressProgressTag;
@@ -246,12 +317,50 @@ pub(crate) struct PlantInstantTag {
     pub effect_timer: Vec<Timer>,
 }
 
+// 睡着的夜行植物，不会攻击和生产
+#[derive(Component)]
+pub(crate) struct PlantSleepTag {
+    // 头顶的睡眠提示，唤醒时移除
+    pub marker: Entity,
+}
+
+// 临时植物（咖啡豆），计时结束后唤醒同一格子中的植物并消失
+#[derive(Component)]
+pub(crate) struct PlantWakeUpTag {
+    pub timer: Timer,
+}
+
+// 墓碑吞噬者，计时结束后移除所在格子的墓碑并消失
+#[derive(Component)]
+pub(crate) struct PlantConsumeGraveTag {
+    pub timer: Timer,
+}
+
 #[derive(Component)]
 pub(crate) struct ExplodeEffectTag {
     pub radius: f32,
     pub damage: f32,
 }
 
+#[derive(Component)]
+pub(crate) struct FreezeEffectTag {
+    pub radius: f32,
+    pub duration: f32,
+}
+
+#[derive(Component)]
+pub(crate) struct CraterEffectTag {
+    pub solt: Entity,
+    pub duration: f32,
+}
+
+// 弹坑，随时间逐渐变小，消失后格子可以重新种植
+#[derive(Component)]
+pub(crate) struct CraterTag {
+    pub solt: Entity,
+    pub timer: Timer,
+}
+
 // 植物是否要进行攻击（是否侦测到攻击范围内有敌人）
 #[derive(Component)]
 pub(crate) enum PlantShootTag {
@@ -261,7 +370,28 @@ pub(crate) enum PlantShootTag {
 
 // 投掷物标记
 #[derive(Component)]
-pub(crate) struct ProjectileTag;
+pub(crate) struct ProjectileTag {
+    pub info: Arc<ProjectileInfo>,
+    // 剩余可穿透的僵尸数量
+    pub pierce: u32,
+    // 已命中的僵尸，穿透时不重复结算
+    pub hit_zombies: HashSet<Entity>,
+}
+
+// 抛物线投掷物，下落时只与所在路的僵尸碰撞，落地后移除
+#[derive(Component)]
+pub(crate) struct ThrowProjectileTag {
+    // 落点高度
+    pub land_y: f32,
+}
+
+// 追踪投掷物，目标死亡或进入临界状态后重新寻找目标
+#[derive(Component)]
+pub(crate) struct FollowProjectileTag {
+    pub target: Option<Entity>,
+    // 每秒最大转向角度（弧度）
+    pub turn_rate: f32,
+}
 
 // 移动速度
 #[derive(Component)]
@@ -297,6 +427,52 @@ pub(crate) struct ZombieTag;
 #[derive(Component)]
 pub(crate) struct ZombieAttackableTag;
 
+// 碰撞箱，相对于实体位置
+#[derive(Component)]
+pub(crate) struct Hitbox(pub Rect);
+
+// 僵尸身上的状态效果
+#[derive(Component, Default)]
+pub(crate) struct ZombieStatus(pub Vec<ZombieStatusEffect>);
+
+pub(crate) struct ZombieStatusEffect {
+    pub effect_type: StatusEffectType,
+    pub timer: Timer,
+    // 减速时为速度倍率，灼烧时为每秒伤害
+    pub strength: f32,
+}
+
+// 僵尸剩余跳跃次数
+#[derive(Component)]
+pub(crate) struct ZombieJumpTag {
+    pub times: u8,
+}
+
+// 僵尸正在跳跃
+#[derive(Component)]
+pub(crate) struct ZombieJumpingTag {
+    pub state: ZombieJumpState,
+    pub timer: Timer,
+}
+
+pub(crate) enum ZombieJumpState {
+    // 前摇，原地不动
+    Precast,
+    // 越过植物
+    Jumping,
+}
+
+// 飞行中的僵尸，只能被追踪投掷物命中
+#[derive(Component)]
+pub(crate) struct ZombieFlyingTag;
+
+// 在水路中游泳的僵尸
+#[derive(Component)]
+pub(crate) struct ZombieSwimTag {
+    // 水面的波纹，上岸时移除
+    pub ripple: Entity,
+}
+
 // 僵尸正在啃食状态，并标记啃食的植物
 #[derive(Component)]
 pub(crate) struct ZombieEatTag {
@@ -363,6 +539,12 @@ pub(crate) struct SunshineTag {
     pub count: u32,
 }
 
+// 僵尸掉落的金币
+#[derive(Component)]
+pub(crate) struct CoinTag {
+    pub value: u32,
+}
+
 // 阳光生产槽
 #[derive(Component)]
 pub(crate) struct NaturalSunshineSolt {
@@ -393,6 +575,38 @@ pub(crate) struct FollowCameraTag;
 #[derive(Component)]
 pub(crate) struct StartGameButtonTag;
 
+// 游戏速度按钮
+#[derive(Component)]
+pub(crate) struct SpeedButtonTag;
+
+// 游戏速度文本
+#[derive(Component)]
+pub(crate) struct SpeedTextTag;
+
+// 暂停菜单
+#[derive(Component)]
+pub(crate) struct PauseMenuTag;
+
+// 联机出错的提示
+#[derive(Component)]
+pub(crate) struct NetErrorTag;
+
+// 暂停菜单：继续游戏按钮
+#[derive(Component)]
+pub(crate) struct ResumeButtonTag;
+
+// 暂停菜单：重新开始按钮
+#[derive(Component)]
+pub(crate) struct RestartButtonTag;
+
+// 暂停菜单：选项按钮
+#[derive(Component)]
+pub(crate) struct OptionsButtonTag;
+
+// 暂停菜单：主菜单按钮
+#[derive(Component)]
+pub(crate) struct MainMenuButtonTag;
+
 // 可以进行选择的种子
 #[derive(Component)]
 pub(crate) struct ChooseableSeedTag;
@@ -423,6 +637,14 @@ impl PlantSolt {
         [self.temp, self.protect, self.primary, self.pot]
     }
 
+    // 铲子移除的植物：临时植物、主要植物、保护类植物、花盆类植物
+    pub fn shovel_target(&self) -> Option<Entity> {
+        [self.temp, self.primary, self.protect, self.pot]
+            .into_iter()
+            .flatten()
+            .next()
+    }
+
     pub fn plants_mut(&mut self) -> [&mut Option<Entity>; 4] {
         [
             &mut self.temp,
@@ -432,3 +654,91 @@ impl PlantSolt {
         ]
     }
 }
+
+impl Hitbox {
+    // 实体位于 position 时的碰撞箱
+    pub fn at(&self, position: Vec2) -> Rect {
+        Rect {
+            min: position + self.0.min,
+            max: position + self.0.max,
+        }
+    }
+}
+
+impl ZombieStatus {
+    // 施加状态效果
+    // 同类效果刷新持续时间并保留较强的一方，灼烧与减速、冰冻互相抵消
+    pub fn apply(&mut self, effect_type: StatusEffectType, duration: f32, strength: f32) {
+        match effect_type {
+            StatusEffectType::Burn => self.0.retain(|effect| {
+                !matches!(
+                    effect.effect_type,
+                    StatusEffectType::Slow | StatusEffectType::Freeze
+                )
+            }),
+            StatusEffectType::Slow | StatusEffectType::Freeze => self
+                .0
+                .retain(|effect| effect.effect_type != StatusEffectType::Burn),
+            StatusEffectType::Butter => {}
+        }
+
+        let duration = Duration::from_secs_f32(duration);
+        if let Some(effect) = self
+            .0
+            .iter_mut()
+            .find(|effect| effect.effect_type == effect_type)
+        {
+            if effect.timer.remaining() < duration {
+                effect.timer = Timer::new(duration, TimerMode::Once);
+            }
+            effect.strength = match effect_type {
+                StatusEffectType::Slow => effect.strength.min(strength),
+                _ => effect.strength.max(strength),
+            };
+        } else {
+            self.0.push(ZombieStatusEffect {
+                effect_type,
+                timer: Timer::new(duration, TimerMode::Once),
+                strength,
+            });
+        }
+    }
+
+    pub fn has(&self, effect_type: StatusEffectType) -> bool {
+        self.0
+            .iter()
+            .any(|effect| effect.effect_type == effect_type)
+    }
+
+    // 移动速度倍率
+    pub fn speed_factor(&self) -> f32 {
+        self.0
+            .iter()
+            .map(|effect| match effect.effect_type {
+                StatusEffectType::Slow => effect.strength,
+                StatusEffectType::Freeze | StatusEffectType::Butter => 0.0,
+                StatusEffectType::Burn => 1.0,
+            })
+            .fold(1.0, f32::min)
+    }
+
+    // 啃食速度倍率，与移动速度相同
+    pub fn eat_factor(&self) -> f32 {
+        self.speed_factor()
+    }
+
+    // 骨骼染色，冰冻 > 黄油 > 减速 > 灼烧
+    pub fn tint(&self) -> Color {
+        if self.has(StatusEffectType::Freeze) {
+            Color::srgb(0.6, 0.8, 1.0)
+        } else if self.has(StatusEffectType::Butter) {
+            Color::srgb(1.0, 1.0, 0.6)
+        } else if self.has(StatusEffectType::Slow) {
+            Color::srgb(0.5, 0.5, 1.0)
+        } else if self.has(StatusEffectType::Burn) {
+            Color::srgb(1.0, 0.6, 0.4)
+        } else {
+            Color::WHITE
+        }
+    }
+}
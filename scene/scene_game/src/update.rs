@@ -1,10 +1,19 @@
-use std::{any::TypeId, time::Duration};
+use std::{
+    any::TypeId,
+    f32::consts::{PI, TAU},
+    sync::Arc,
+    time::Duration,
+};
 
 use bevy::{
-    asset::AssetPath, audio::PlaybackMode, ecs::system::SystemId, prelude::*, sprite::Anchor,
+    asset::AssetPath,
+    audio::PlaybackMode,
+    ecs::system::SystemId,
+    prelude::*,
+    sprite::{Anchor, MaterialMesh2dBundle},
     utils::hashbrown::HashSet,
+    window::WindowFocused,
 };
-use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
 use bevy_spine::{SkeletonData, Spine, SpineBundle};
 use consts::anim::{INDEX_SUN_FADE_OUT, NAME_SUN_FADE_OUT};
 use fw_actor::{
@@ -14,20 +23,22 @@ use fw_actor::{
 use fw_anim::{AnimationBundle, AnimationClip, AnimationClips, CustomAnimationTrigger, KeyFrame};
 use fw_button::components::{Button, ButtonEnabled, ButtonInteraction};
 use fw_cursor::CursorPosition;
-use fw_ftxm::FtxmAudioSink;
-use mod_item::{ItemRegistry, ItemType};
+use fw_ftxm::{FtxmAudioSink, FtxmSource, MainMusicTable};
+use mod_item::ItemRegistry;
 use mod_level::{
+    note::{NoteRegistry, NoteUnlock},
     CurrentLevel, LevelBackground, LevelType, Reward, SodType, SpecialRule, WaveType, Zombie,
 };
+use mod_net::{NetSession, VersusCommand};
 use mod_plant::{
     components::{
         AnimPlantDamage1Tag, AnimPlantDamage2Tag, AnimPlantInstantTag, AnimPlantProduceTag,
-        AnimPlantShootTag, PlantBundle, PlantCooldown, PlantHp, PlantMetaData, PlantSeed,
-        PlantSeedBundle, PlantUsable, SeedHover, SunshineVisibility,
+        AnimPlantShootTag, AnimPlantSleepTag, PlantBundle, PlantCooldown, PlantHp, PlantMetaData,
+        PlantSeed, PlantSeedBundle, PlantUsable, SeedHover, SunshineVisibility,
     },
     metadata::{
-        InstantEffectType, Particle, PlantDetect, PlantPosition, PlantRegistry, PlantType,
-        ProjectileTrack, ProjectileType, ResourceType,
+        InstantEffectType, Particle, PlantDetect, PlantInfo, PlantPosition, PlantRegistry,
+        PlantType, ProjectileRegistry, ProjectileTrack, ResourceType, StatusEffectType,
     },
 };
 use mod_userdata::UserData;
@@ -35,30 +46,43 @@ use mod_zombie::{
     components::{
         AnimZombieArmor1Tag, AnimZombieArmor2Tag, AnimZombieArmor3Tag, AnimZombieCriticalTag,
         AnimZombieEatStopTag, AnimZombieEatTag, AnimZombieFullDamageTag, AnimZombieHalfDamageTag,
-        AnimZombieMoveTag, ZombieBundle, ZombieHp, ZombieMetadata,
+        AnimZombieJumpTag, AnimZombieMoveTag, AnimZombieSwimTag, ZombieBundle, ZombieHp,
+        ZombieMetadata,
     },
-    metadata::ZombieRegistry,
+    metadata::{Swim, ZombieInfo, ZombieRegistry, ZombieType},
+};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
 };
-use rand::{seq::SliceRandom, thread_rng, Rng};
 use scene_base::GameScene;
 
 use crate::{
-    resource::ZombieWaveController,
+    replay::PlayerAction,
+    resource::{GameRng, GameSpeed, ZombieWaveController},
+    setup::lane_y,
+    spatial::LaneIndex,
     tag::{
         BootCleanerCar, BowlingHitCooldown, BowlingHitZombieMemory, CherryBombParticleTag,
-        ChooseableSeedTag, CleanerCar, ColorAlphaFade, ConveyorBeltAnimTag, ConveyorBeltSeedTag,
-        ConveyorBeltSolt, ConveyorBeltTag, DelayShow, ExplodeEffectTag, FollowCameraTag,
-        FollowCursorTag, Freeze, GameTimer, GameTimerTag, GameUiTag, ImageCutAnim, InvincibleTag,
-        LanePosition, LevelProgressFlagTag, LevelProgressHeadTag, LevelProgressProgressTag,
-        MaterialColorAnim, MoveAcceleration, MoveTimer, MoveVelocity, NaturalSunshineSolt,
-        NaturalSunshineTag, OnetimeSeedTag, PickSeed, PickableSeed, PlantHpAnim, PlantInstantTag,
-        PlantProduceTag, PlantShootTag, PlantSolt, PlantTag, ProjectileCooldown, ProjectileTag,
-        RemoveOutrangeTag, RewardSolt, RewardTag, RotateTag, SceneTag, SeedChooserTag,
-        SeedTransformInChooserBox, SeedbankTag, ShowLevelProgressShiftLeft, SoltType,
-        StartGameButtonTag, SunshineTag, SunshineText, ToDespawn, ToSpawnZombie,
-        ZombieAttackableTag, ZombieCriticalTag, ZombieEatTag, ZombieHpAnim, ZombieSolt, ZombieTag,
+        ChooseableSeedTag, CleanerCar, CoinTag, ColorAlphaFade, ConveyorBeltAnimTag,
+        ConveyorBeltSeedTag, ConveyorBeltSolt, ConveyorBeltTag, CraterEffectTag, CraterTag,
+        DelayShow, ExplodeEffectTag, FollowCameraTag, FollowCursorTag, FollowProjectileTag, Freeze,
+        FreezeEffectTag, GameTimer, GameTimerTag, GameUiTag, GraveTag, Hitbox, ImageCutAnim,
+        InvincibleTag, LanePosition, LevelProgressFlagTag, LevelProgressHeadTag,
+        LevelProgressProgressTag, LevelProgressTag, MainMenuButtonTag, MaterialColorAnim,
+        MoveAcceleration, MoveTimer, MoveVelocity, NaturalSunshineSolt, NaturalSunshineTag,
+        OnetimeSeedTag, PickSeed, PickShovel, PickZombieSeed, PickableSeed, PlantConsumeGraveTag,
+        PlantHpAnim, PlantInstantTag, PlantProduceTag, PlantShootTag, PlantSleepTag, PlantSolt,
+        PlantTag, PlantWakeUpTag, ProjectileCooldown, ProjectileTag, RemoveOutrangeTag,
+        RestartButtonTag, ResumeButtonTag, RewardSolt, RewardTag, RotateTag, SceneTag,
+        SeedChooserTag, SeedTransformInChooserBox, SeedbankTag, ShovelBankTag, ShovelTag,
+        ShowLevelProgressShiftLeft, SoltType, SpeedButtonTag, SpeedTextTag, StartGameButtonTag,
+        SunshineTag, SunshineText, ThrowProjectileTag, ToDespawn, ToSpawnZombie, VersusTarget,
+        ZombieAttackableTag, ZombieCriticalTag, ZombieEatTag, ZombieFlyingTag, ZombieHpAnim,
+        ZombieJumpState, ZombieJumpTag, ZombieJumpingTag, ZombieSolt, ZombieStatus, ZombieSwimTag,
+        ZombieTag,
     },
-    GameState, Sunshine,
+    versus, GameState, Sunshine,
 };
 
 pub(crate) struct UpdateTimerSystem {
@@ -300,11 +324,17 @@ fn trigger_fade_in_conveyor_belt(
 fn trigger_fade_in_cars(
     mut commands: Commands,
     mut cars: Query<(Entity, &mut Visibility, &CleanerCar)>,
+    current_level: Res<CurrentLevel>,
 ) {
     let mut clips = Vec::new();
     for (entity, mut visiblity, car) in &mut cars {
+        // 生存模式下每轮都会进入战场，已出现的车不再重复
+        if matches!(*visiblity, Visibility::Inherited) {
+            continue;
+        }
+
         *visiblity = Visibility::Inherited;
-        let y = car.lane as f32 * 100.0 - 100.0 * 2.;
+        let y = lane_y(&current_level.background, car.lane) + 20.0;
 
         clips.push(AnimationClip {
             entity,
@@ -414,7 +444,7 @@ fn trigger_fade_out_seed_chooser(
 fn trigger_show_level_progress(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    current_level: Res<CurrentLevel>,
+    zombie_wave_controller: Res<ZombieWaveController>,
     mut shift_left: Query<&mut Transform, With<ShowLevelProgressShiftLeft>>,
 ) {
     let meter = asset_server.load("images/FlagMeter.png");
@@ -437,6 +467,7 @@ fn trigger_show_level_progress(
             ..Default::default()
         },
         SceneTag,
+        LevelProgressTag,
     ));
 
     // 进度条
@@ -455,11 +486,12 @@ fn trigger_show_level_progress(
             ..Default::default()
         },
         SceneTag,
+        LevelProgressTag,
         LevelProgressProgressTag,
     ));
 
     // 旗帜
-    let flag_count = current_level
+    let flag_count = zombie_wave_controller
         .waves
         .iter()
         .filter(|wave| matches!(wave.wave_type, WaveType::HugeWave))
@@ -485,6 +517,7 @@ fn trigger_show_level_progress(
                 ..Default::default()
             },
             SceneTag,
+            LevelProgressTag,
         ));
         commands.spawn((
             SpriteBundle {
@@ -501,6 +534,7 @@ fn trigger_show_level_progress(
                 ..Default::default()
             },
             SceneTag,
+            LevelProgressTag,
             LevelProgressFlagTag::NotReach,
         ));
     }
@@ -517,6 +551,7 @@ fn trigger_show_level_progress(
             ..Default::default()
         },
         SceneTag,
+        LevelProgressTag,
     ));
 
     // 头
@@ -535,6 +570,7 @@ fn trigger_show_level_progress(
             ..Default::default()
         },
         SceneTag,
+        LevelProgressTag,
         LevelProgressHeadTag,
     ));
 
@@ -646,13 +682,10 @@ fn trigger_sound_lose(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 fn trigger_sound_eat(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut rng = thread_rng();
-    spawn_se(
+    spawn_random_se(
         &mut commands,
         &asset_server,
-        *["sounds/chomp.ogg", "sounds/chomp2.ogg"]
-            .choose(&mut rng)
-            .unwrap(),
+        &["sounds/chomp.ogg", "sounds/chomp2.ogg"],
     );
 }
 
@@ -889,36 +922,45 @@ fn trigger_finish(
     mut next_screen: ResMut<NextState<GameScene>>,
     mut userdata: ResMut<UserData>,
     level: Res<CurrentLevel>,
+    item_registry: Res<ItemRegistry>,
+    note_registry: Res<NoteRegistry>,
 ) {
     // 获取奖励
     #[allow(clippy::single_match)]
-    match level.reward {
+    match &level.reward {
         Some(Reward::Plant { plant }) => {
-            userdata.unlock_plugins.insert(plant);
+            userdata.unlock_plugins.insert(*plant);
         }
-        Some(Reward::Item { item }) => match item {
-            ItemType::Shovel => {
-                userdata.unlock_shovel = true;
-            }
-            ItemType::Almanac => {
-                userdata.unlock_almanac = true;
-            }
-            ItemType::Key => {
-                userdata.unlock_shop = true;
-            }
-            ItemType::Taco => {
-                userdata.unlock_shop_taco_upgrade = true;
+        Some(Reward::Item { item }) => {
+            if let Some(item_info) = item_registry.get(item) {
+                userdata.obtain_item(item_info);
             }
-            ItemType::WateringCan => {
-                userdata.unlock_zen_garden = true;
+        }
+        Some(Reward::Note { note_path }) => {
+            // 解锁信件中提到的内容
+            let unlocks = note_registry
+                .get(note_path)
+                .map(|note| note.unlocks.as_slice())
+                .unwrap_or_default();
+            for unlock in unlocks {
+                match unlock {
+                    NoteUnlock::Minigame => userdata.unlock_minigame = true,
+                    NoteUnlock::Puzzle => userdata.unlock_puzzle = true,
+                    NoteUnlock::Survival => userdata.unlock_survial = true,
+                    NoteUnlock::Plant { plant } => {
+                        userdata.unlock_plugins.insert(*plant);
+                    }
+                    NoteUnlock::Zombie { zombie } => {
+                        userdata.seen_zombies.insert(*zombie);
+                    }
+                }
             }
-        },
-        Some(Reward::Note { note_path: _ }) => {}
+        }
         None => {}
     }
 
-    // 冒险模式？进度+1
-    if matches!(level.id, LevelType::Adventure { level: _ }) {
+    // 冒险模式？进度+1，重玩已通关的关卡不增加进度
+    if matches!(level.id, LevelType::Adventure { level } if level == userdata.adventure_progress) {
         userdata.adventure_progress += 1;
         if userdata.adventure_progress > 50 {
             userdata.adventure_progress = 0;
@@ -966,7 +1008,7 @@ fn move_camera(
 }
 
 #[inline]
-fn spawn_se(
+pub(crate) fn spawn_se(
     commands: &mut Commands,
     asset_server: &AssetServer,
     path: impl Into<AssetPath<'static>>,
@@ -984,6 +1026,18 @@ fn spawn_se(
     ));
 }
 
+// 随机播放其中一个音效，不占用对局随机数，保证回放与联机一致
+pub(crate) fn spawn_random_se(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    paths: &[&'static str],
+) {
+    let Some(path) = paths.choose(&mut rand::thread_rng()) else {
+        return;
+    };
+    spawn_se(commands, asset_server, *path);
+}
+
 #[allow(clippy::too_many_arguments)]
 #[inline]
 fn spawn_image(
@@ -1095,7 +1149,7 @@ pub(crate) fn update_image_cut(
 }
 
 // 输入：捡起种子
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub(crate) fn input_pick_seed(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -1107,12 +1161,15 @@ pub(crate) fn input_pick_seed(
             &PlantUsable,
             &SeedHover,
             Option<&OnetimeSeedTag>,
+            &GlobalTransform,
         ),
         (With<PlantSeed>, With<PickableSeed>),
     >,
     mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
     pick_seed: Query<(Entity, &PickSeed)>,
+    pick_shovel: Query<Entity, With<PickShovel>>,
     sunshine: Res<Sunshine>,
+    mut actions: EventWriter<PlayerAction>,
 ) {
     // 左键点击
     if !mouse_button_input.just_pressed(MouseButton::Left) {
@@ -1120,15 +1177,26 @@ pub(crate) fn input_pick_seed(
     }
 
     // 点击的种子卡
-    let Some((entity, PlantMetaData(metadata), cooldown, usable, _, onetime)) = seed
+    let Some((entity, PlantMetaData(metadata), cooldown, usable, _, onetime, transform)) = seed
         .iter()
-        .find(|(_, _, _, _, hover, _)| matches!(hover, SeedHover::Hover))
+        .find(|(_, _, _, _, hover, _, _)| matches!(hover, SeedHover::Hover))
     else {
         return;
     };
 
     // 如果点到了种子卡，则清除点击事件
     mouse_button_input.clear_just_pressed(MouseButton::Left);
+    let translation = transform.translation();
+    actions.send(PlayerAction::PickSeed {
+        plant: metadata.id,
+        x: translation.x,
+        y: translation.y,
+    });
+
+    // 放下捡起的铲子
+    for entity in &pick_shovel {
+        commands.entity(entity).despawn_recursive();
+    }
 
     // 正在捡起的种子
     for (pick_seed_entity, PickSeed { seed, .. }) in &pick_seed {
@@ -1216,9 +1284,13 @@ pub(crate) fn plant_seed(
     >,
     cursor_position: Res<CursorPosition>,
     mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
-    plants: Query<&PlantMetaData>,
+    plants: Query<(&PlantMetaData, Has<PlantSleepTag>)>,
     asset_server: Res<AssetServer>,
     mut sunshine: ResMut<Sunshine>,
+    mut rng: ResMut<GameRng>,
+    mut actions: EventWriter<PlayerAction>,
+    session: Option<Res<NetSession>>,
+    mut versus_commands: EventWriter<VersusCommand>,
 ) {
     if !mouse_button_input.just_pressed(MouseButton::Left) {
         return;
@@ -1257,12 +1329,91 @@ pub(crate) fn plant_seed(
 
     // 如果点到了格子，清除点击事件
     mouse_button_input.clear_just_pressed(MouseButton::Left);
+    actions.send(PlayerAction::Plant {
+        x: solt_translation.x,
+        y: solt_translation.y,
+    });
+
+    // 检查植物种植位置
+    let Some(solt_position) = plant_solt_position(&mut solt, plant_info, &plants) else {
+        return;
+    };
+
+    // 对应位置已有植物，忽略，升级植物则需要种在对应的植物上
+    if !can_plant_at(solt_position, plant_info, &plants) {
+        return;
+    }
+
+    // 再次检查阳光是否充足
+    if sunshine.0 < plant_info.sunshine {
+        return;
+    }
+
+    // 移除跟随鼠标的植物
+    commands.entity(entity).despawn_recursive();
+
+    // 联机时只发送操作，在双方的同一帧种植
+    if session.is_some() {
+        versus_commands.send(VersusCommand::PlantSeed {
+            plant: plant_info.id,
+            column: versus::cell_column(solt_translation.x),
+            lane: lane_position.lane,
+        });
+        return;
+    }
+
+    // 升级植物替换原有的植物
+    if let Some(plant) = solt_position.take() {
+        commands.entity(plant).despawn_recursive();
+    }
+
+    // 在格子位置种植物
+    *solt_position = Some(spawn_plant(
+        &mut commands,
+        &asset_server,
+        &mut rng,
+        plant_info,
+        solt_entity,
+        solt_translation,
+        lane_position.lane,
+    ));
+
+    if let Ok((entity, mut cooldown, onetime)) = seeds.get_mut(*seed) {
+        // 如果是一次性植物，则移除
+        if onetime.is_some() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            // 扣除阳光
+            sunshine.0 -= plant_info.sunshine;
+
+            // 种子重新装填
+            *cooldown = PlantCooldown::Cooldown(Duration::from_secs_f32(plant_info.cooldown));
+        }
+    }
+}
+
+// 格子中可以种植该植物的位置，无法种植时返回 None
+pub(crate) fn plant_solt_position<'a>(
+    solt: &'a mut PlantSolt,
+    plant_info: &PlantInfo,
+    plants: &Query<(&PlantMetaData, Has<PlantSleepTag>)>,
+) -> Option<&'a mut Option<Entity>> {
+    // 临时植物（咖啡豆）只能种在有睡着的植物的格子中
+    if matches!(plant_info.position, PlantPosition::Temp)
+        && !solt
+            .plants()
+            .into_iter()
+            .flatten()
+            .any(|entity| plants.get(entity).is_ok_and(|(_, sleeping)| sleeping))
+    {
+        return None;
+    }
 
-    // 检查植物种植位置，有花盆优先考虑花盆
+    // 有花盆优先考虑花盆
     let pot_plant = solt
         .pot
         .and_then(|entity| plants.get(entity).ok())
-        .map(|metadata| metadata.0.id);
+        .map(|(metadata, _)| metadata.0.id);
     let can_plant_on = if solt.grave.is_some() {
         plant_info.plant_on.grave
     } else if solt.hole.is_some() {
@@ -1278,34 +1429,51 @@ pub(crate) fn plant_seed(
         }
     };
     if !can_plant_on {
-        return;
+        return None;
     }
 
     // 对应植物槽位置
-    let solt_position = match &plant_info.position {
+    Some(match &plant_info.position {
         PlantPosition::Primary => &mut solt.primary,
         PlantPosition::Protect => &mut solt.protect,
         PlantPosition::Pot => &mut solt.pot,
         PlantPosition::Temp => &mut solt.temp,
-    };
-
-    // 对应位置已有植物，忽略
-    if solt_position.is_some() {
-        return;
-    }
+    })
+}
 
-    // 再次检查阳光是否充足
-    if sunshine.0 < plant_info.sunshine {
-        return;
+// 格子中对应位置能否种植该植物，升级植物需要种在对应的植物上，其余植物需要空位
+pub(crate) fn can_plant_at(
+    solt_position: &Option<Entity>,
+    plant_info: &PlantInfo,
+    plants: &Query<(&PlantMetaData, Has<PlantSleepTag>)>,
+) -> bool {
+    match (*solt_position, plant_info.upgrade_from) {
+        (None, None) => true,
+        (Some(entity), Some(upgrade_from)) => plants
+            .get(entity)
+            .is_ok_and(|(PlantMetaData(metadata), _)| metadata.id == upgrade_from),
+        _ => false,
     }
+}
 
-    // 移除跟随鼠标的植物
-    commands.entity(entity).despawn_recursive();
-
-    // 在格子位置种植物
+// 在格子位置种植物，返回植物实体
+pub(crate) fn spawn_plant(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    rng: &mut GameRng,
+    plant_info: &Arc<PlantInfo>,
+    solt_entity: Entity,
+    solt_translation: Vec3,
+    lane: u8,
+) -> Entity {
     let mut plant_bundle = PlantBundle::new(plant_info.clone());
+    // 花盆类植物显示在其他植物下方
+    let z = match plant_info.position {
+        PlantPosition::Pot => 9.9,
+        _ => 10.0,
+    };
     plant_bundle.spine.transform =
-        Transform::from_xyz(solt_translation.x, solt_translation.y - 15.0, 10.0)
+        Transform::from_xyz(solt_translation.x, solt_translation.y - 15.0, z)
             .with_scale(Vec3::ONE * 0.7);
     let plant_entity = commands
         .spawn((
@@ -1317,7 +1485,7 @@ pub(crate) fn plant_seed(
             AnimStandbyTag,
             SceneTag,
             LanePosition {
-                lane: lane_position.lane,
+                lane,
                 x: solt_translation.x,
             },
         ))
@@ -1336,7 +1504,7 @@ pub(crate) fn plant_seed(
     // 植物生产标记
     if let Some(produce) = &plant_info.produce {
         commands.entity(plant_entity).insert(PlantProduceTag {
-            cooldown: produce.cooldown + thread_rng().gen_range(0.0..produce.cooldown_spread)
+            cooldown: produce.cooldown + rng.gen_range(0.0..produce.cooldown_spread)
                 - produce.cooldown_spread / 2.0,
             elaspse: 0.0,
         });
@@ -1363,47 +1531,35 @@ pub(crate) fn plant_seed(
         ));
         // 音效
         if let Some(se) = &instant.enter_sound {
-            spawn_se(&mut commands, &asset_server, se.to_owned());
+            spawn_se(commands, asset_server, se.to_owned());
         }
     }
     // 植物血量皮肤
     if plant_info.render.damage_skin {
         commands.entity(plant_entity).insert(PlantHpAnim::default());
     }
-    *solt_position = Some(plant_entity);
-
-    if let Ok((entity, mut cooldown, onetime)) = seeds.get_mut(*seed) {
-        // 如果是一次性植物，则移除
-        if onetime.is_some() {
-            commands.entity(entity).despawn_recursive();
-        } else {
-            // 扣除阳光
-            sunshine.0 -= plant_info.sunshine;
-
-            // 种子重新装填
-            *cooldown = PlantCooldown::Cooldown(Duration::from_secs_f32(plant_info.cooldown));
-        }
-    }
 
     // 音效
-    spawn_se(
-        &mut commands,
-        &asset_server,
-        *["sounds/plant.ogg", "sounds/plant2.ogg"]
-            .choose(&mut thread_rng())
-            .unwrap(),
+    spawn_random_se(
+        commands,
+        asset_server,
+        &["sounds/plant.ogg", "sounds/plant2.ogg"],
     );
+
+    plant_entity
 }
 
-// 取消捡起的种子
+// 取消捡起的种子或铲子
 pub(crate) fn cancel_pick_seed(
     mut commands: Commands,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    seed: Query<Entity, With<PickSeed>>,
+    seed: Query<Entity, Or<(With<PickSeed>, With<PickShovel>, With<PickZombieSeed>)>>,
+    mut actions: EventWriter<PlayerAction>,
 ) {
-    if !mouse_button_input.just_pressed(MouseButton::Right) {
+    if !mouse_button_input.just_pressed(MouseButton::Right) || seed.is_empty() {
         return;
     }
+    actions.send(PlayerAction::CancelSeed);
 
     for entity in &seed {
         commands.entity(entity).despawn_recursive();
@@ -1411,20 +1567,39 @@ pub(crate) fn cancel_pick_seed(
 }
 
 // 植物射击
+#[allow(clippy::type_complexity)]
 pub(crate) fn plant_shoot(
     mut commands: Commands,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
-    mut plants: Query<(
-        Entity,
-        &mut ProjectileCooldown,
-        &PlantShootTag,
-        &PlantTag,
-        &GlobalTransform,
-    )>,
+    projectile_registry: Res<ProjectileRegistry>,
+    mut plants: Query<
+        (
+            Entity,
+            &mut ProjectileCooldown,
+            &PlantShootTag,
+            &PlantTag,
+            &GlobalTransform,
+            &LanePosition,
+        ),
+        Without<PlantSleepTag>,
+    >,
+    zombies: Query<
+        (
+            &LanePosition,
+            &GlobalTransform,
+            Option<&MoveVelocity>,
+            Has<Freeze>,
+        ),
+        (
+            With<ZombieAttackableTag>,
+            Without<ZombieCriticalTag>,
+            Without<ZombieFlyingTag>,
+        ),
+    >,
 ) {
     let delta = time.delta().as_secs_f32();
-    for (entity, mut cooldown, shoot, plant, global_transform) in &mut plants {
+    for (entity, mut cooldown, shoot, plant, global_transform, plant_position) in &mut plants {
         // 射击冷却
         cooldown.cooldown -= delta;
         if cooldown.cooldown > 0.0 {
@@ -1450,16 +1625,13 @@ pub(crate) fn plant_shoot(
             .map(|shoot| shoot.projectiles.iter())
             .unwrap_or_default()
         {
-            let projectile_image = asset_server.load(match &projectile.projectile_type {
-                ProjectileType::Pea => "images/ProjectilePea.png",
-                ProjectileType::SnowPea => "images/ProjectileSnowPea.png",
-                ProjectileType::Cactus => "images/ProjectileCactus.png",
-                ProjectileType::Star => "images/Projectile_star.png",
-            });
+            let Some(projectile_info) = projectile_registry.get(&projectile.projectile_type) else {
+                continue;
+            };
 
             let mut projectile_entity = commands.spawn((
                 SpriteBundle {
-                    texture: projectile_image,
+                    texture: asset_server.load(&projectile_info.texture),
                     transform: Transform::from_translation(
                         plant_original
                             + Vec3 {
@@ -1472,7 +1644,11 @@ pub(crate) fn plant_shoot(
                     ..Default::default()
                 },
                 SceneTag,
-                ProjectileTag,
+                ProjectileTag {
+                    info: projectile_info.clone(),
+                    pierce: projectile_info.pierce,
+                    hit_zombies: HashSet::default(),
+                },
                 RemoveOutrangeTag,
                 Freeze,
                 DelayShow {
@@ -1482,7 +1658,7 @@ pub(crate) fn plant_shoot(
                     ),
                     se: Some(
                         *["sounds/throw.ogg", "sounds/throw2.ogg"]
-                            .choose(&mut thread_rng())
+                            .choose(&mut rand::thread_rng())
                             .unwrap(),
                     ),
                 },
@@ -1491,10 +1667,70 @@ pub(crate) fn plant_shoot(
             // 速度
             match &projectile.track {
                 ProjectileTrack::Line { direction } => {
-                    projectile_entity.insert(MoveVelocity(Vec2::from_angle(*direction) * 500.0));
+                    let direction = Vec2::from_angle(*direction);
+                    projectile_entity.insert(MoveVelocity(direction * 500.0));
+
+                    // 水平飞行的投掷物只与所在路的僵尸碰撞
+                    if direction.y.abs() < f32::EPSILON {
+                        projectile_entity.insert(LanePosition {
+                            lane: plant_position.lane,
+                            x: plant_original.x + projectile.offset_x * 0.7,
+                        });
+                    }
+                }
+                ProjectileTrack::Throw => {
+                    // 同一路前方最近的僵尸，不向身后投掷
+                    let Some((_, zombie_transform, zombie_velocity, zombie_freeze)) = zombies
+                        .iter()
+                        .filter(|(zombie_position, ..)| {
+                            zombie_position.lane == plant_position.lane
+                                && zombie_position.x >= plant_position.x
+                        })
+                        .min_by(|(a, ..), (b, ..)| a.x.total_cmp(&b.x))
+                    else {
+                        projectile_entity.despawn();
+                        continue;
+                    };
+
+                    // 起点
+                    let start = Vec2 {
+                        x: plant_original.x + projectile.offset_x * 0.7,
+                        y: plant_original.y + projectile.offset_y * 0.7,
+                    };
+                    // 预测僵尸在落地时的位置
+                    let zombie_translation = zombie_transform.translation();
+                    let mut end = Vec2 {
+                        x: zombie_translation.x,
+                        y: zombie_translation.y + THROW_TARGET_HEIGHT,
+                    };
+                    let throw_time = ((end.x - start.x).abs() / THROW_SPEED_X)
+                        .clamp(THROW_MIN_TIME, THROW_MAX_TIME);
+                    if let (Some(velocity), false) = (zombie_velocity, zombie_freeze) {
+                        end.x += velocity.0.x * (projectile.shoot_timing + throw_time);
+                    }
+
+                    projectile_entity.insert((
+                        MoveVelocity(calculate_throw_initial_velocity(
+                            start, end, -1000.0, throw_time,
+                        )),
+                        MoveAcceleration(Vec2 { x: 0.0, y: -1000.0 }),
+                        ThrowProjectileTag { land_y: end.y },
+                        LanePosition {
+                            lane: plant_position.lane,
+                            x: start.x,
+                        },
+                    ));
+                }
+                ProjectileTrack::Follow { turn_rate } => {
+                    // 初始朝向前方，之后逐帧转向目标
+                    projectile_entity.insert((
+                        MoveVelocity(Vec2::X * 500.0),
+                        FollowProjectileTag {
+                            target: None,
+                            turn_rate: *turn_rate,
+                        },
+                    ));
                 }
-                ProjectileTrack::Throw => todo!(),
-                ProjectileTrack::Follow => todo!(),
             }
         }
     }
@@ -1542,13 +1778,16 @@ pub(crate) fn update_velocity(
 // 更新实体位置
 pub(crate) fn update_movement(
     time: Res<Time>,
-    mut targets: Query<(&MoveVelocity, &mut Transform), Without<Freeze>>,
+    mut targets: Query<(&MoveVelocity, &mut Transform, Option<&ZombieStatus>), Without<Freeze>>,
 ) {
     let delta = time.delta().as_secs_f32();
-    targets.par_iter_mut().for_each(|(vec, ref mut transform)| {
-        transform.translation.x += delta * vec.0.x;
-        transform.translation.y += delta * vec.0.y;
-    });
+    targets
+        .par_iter_mut()
+        .for_each(|(vec, ref mut transform, status)| {
+            let delta = delta * status.map(ZombieStatus::speed_factor).unwrap_or(1.0);
+            transform.translation.x += delta * vec.0.x;
+            transform.translation.y += delta * vec.0.y;
+        });
 }
 
 // 更新实体旋转
@@ -1570,14 +1809,22 @@ pub(crate) fn update_rotation(
 pub(crate) fn check_zombie_eat_start(
     mut commands: Commands,
     mut zombie: Query<
-        (Entity, &GlobalTransform),
+        (
+            Entity,
+            &GlobalTransform,
+            &ZombieMetadata,
+            &LanePosition,
+            Option<&mut ZombieJumpTag>,
+        ),
         (
             With<ZombieTag>,
             Without<ZombieEatTag>,
             Without<ZombieCriticalTag>,
+            Without<ZombieFlyingTag>,
+            Without<ZombieJumpingTag>,
         ),
     >,
-    plant_kdtree: Res<KDTree2<PlantTag>>,
+    plant_index: Res<LaneIndex<PlantTag>>,
     plant_tag: Query<&PlantTag>,
     plant_solt: Query<&PlantSolt>,
     current_level: Res<CurrentLevel>,
@@ -1587,43 +1834,76 @@ pub(crate) fn check_zombie_eat_start(
         return;
     }
 
-    for (zombie_entity, zombie_transform) in &mut zombie {
-        // 僵尸位置
-        let translation = zombie_transform.translation();
-        let zombie_position = Vec2 {
-            x: translation.x,
-            y: translation.y,
-        };
-        // 最近的植物
-        let Some((plant_position, Some(nearest_plant))) =
-            plant_kdtree.nearest_neighbour(zombie_position)
+    for (zombie_entity, zombie_transform, ZombieMetadata(metadata), lane_position, jump) in
+        &mut zombie
+    {
+        // 同一路最近的植物
+        let zombie_x = zombie_transform.translation().x;
+        let Some((plant_position, nearest_plant)) =
+            plant_index.nearest_in_lane(lane_position.lane, zombie_x)
         else {
             continue;
         };
 
         // 距离判断
-        if zombie_position.distance_squared(plant_position) > 40.0 * 40.0 {
+        if (plant_position.x - zombie_x).abs() > metadata.bite_range {
             continue;
         }
 
         // 从槽位判断应该吃的植物
-        let Ok(plant_tag) = plant_tag.get(nearest_plant) else {
+        let Ok(nearest_plant_tag) = plant_tag.get(nearest_plant) else {
             continue;
         };
-        let Ok(plant_solt) = plant_solt.get(plant_tag.solt) else {
+        let Ok(plant_solt) = plant_solt.get(nearest_plant_tag.solt) else {
             continue;
         };
         let Some(eat_plant) = plant_solt.plants().into_iter().flatten().next() else {
             continue;
         };
 
+        // 跳跃
+        if let (Some(mut jump), Some(jump_info)) =
+            (jump.filter(|jump| jump.times > 0), &metadata.jump)
+        {
+            let tall = plant_solt
+                .plants()
+                .into_iter()
+                .flatten()
+                .any(|plant| plant_tag.get(plant).is_ok_and(|tag| tag.metadata.tall));
+
+            if !tall {
+                commands
+                    .entity(zombie_entity)
+                    .remove::<(AnimZombieMoveTag, AnimZombieSwimTag)>()
+                    .insert((
+                        AnimZombieJumpTag,
+                        ZombieJumpingTag {
+                            state: ZombieJumpState::Precast,
+                            timer: Timer::new(
+                                Duration::from_secs_f32(jump_info.precast_delay),
+                                TimerMode::Once,
+                            ),
+                        },
+                        Freeze,
+                    ));
+                continue;
+            }
+
+            // 被高大植物阻挡，失去跳跃能力，改变速度后开始啃食
+            jump.times = 0;
+            commands.entity(zombie_entity).insert(MoveVelocity(Vec2 {
+                x: -jump_info.speed.unwrap_or(metadata.speed),
+                y: 0.0,
+            }));
+        }
+
         // 僵尸啃食标签
         let mut timer = Timer::new(Duration::from_secs_f32(0.5), TimerMode::Repeating);
         timer.set_elapsed(Duration::from_secs_f32(0.5));
 
         commands
             .entity(zombie_entity)
-            .remove::<AnimZombieMoveTag>()
+            .remove::<(AnimZombieMoveTag, AnimZombieSwimTag)>()
             .insert((
                 AnimZombieEatTag,
                 ZombieEatTag {
@@ -1635,6 +1915,80 @@ pub(crate) fn check_zombie_eat_start(
     }
 }
 
+// 僵尸跳跃
+pub(crate) fn update_zombie_jump(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut zombies: Query<
+        (
+            Entity,
+            &ZombieMetadata,
+            &mut ZombieJumpingTag,
+            &mut ZombieJumpTag,
+            &ZombieStatus,
+        ),
+        Without<ZombieCriticalTag>,
+    >,
+) {
+    for (entity, ZombieMetadata(metadata), mut jumping, mut jump, status) in &mut zombies {
+        // 减速与冰冻同样影响跳跃
+        jumping
+            .timer
+            .tick(time.delta().mul_f32(status.speed_factor()));
+        if !jumping.timer.just_finished() {
+            continue;
+        }
+
+        match jumping.state {
+            ZombieJumpState::Precast => {
+                // 前摇结束，越过植物
+                jumping.state = ZombieJumpState::Jumping;
+                jumping.timer =
+                    Timer::new(Duration::from_secs_f32(ZOMBIE_JUMP_TIME), TimerMode::Once);
+                commands
+                    .entity(entity)
+                    .remove::<Freeze>()
+                    .insert(MoveVelocity(Vec2 {
+                        x: -ZOMBIE_JUMP_DISTANCE / ZOMBIE_JUMP_TIME,
+                        y: 0.0,
+                    }));
+            }
+            ZombieJumpState::Jumping => {
+                // 落地，扣除跳跃次数并改变速度
+                jump.times = jump.times.saturating_sub(1);
+                let speed = metadata
+                    .jump
+                    .as_ref()
+                    .and_then(|jump| jump.speed)
+                    .unwrap_or(metadata.speed);
+                commands
+                    .entity(entity)
+                    .remove::<ZombieJumpingTag>()
+                    .insert((AnimZombieMoveTag, MoveVelocity(Vec2 { x: -speed, y: 0.0 })));
+            }
+        }
+    }
+}
+
+// 跳跃中进入临界状态，取消跳跃并恢复正常移动，不消耗跳跃次数
+pub(crate) fn cancel_critical_zombie_jump(
+    mut commands: Commands,
+    zombies: Query<(Entity, &ZombieMetadata), (With<ZombieJumpingTag>, With<ZombieCriticalTag>)>,
+) {
+    for (entity, ZombieMetadata(metadata)) in &zombies {
+        commands
+            .entity(entity)
+            .remove::<(ZombieJumpingTag, Freeze)>()
+            .insert((
+                AnimZombieMoveTag,
+                MoveVelocity(Vec2 {
+                    x: -metadata.speed,
+                    y: 0.0,
+                }),
+            ));
+    }
+}
+
 // 僵尸结束啃食状态
 pub(crate) fn check_zombie_eat_end(
     mut commands: Commands,
@@ -1652,64 +2006,422 @@ pub(crate) fn check_zombie_eat_end(
     }
 }
 
-// 僵尸结算投掷物伤害
-pub(crate) fn zombie_projectile_damage(
+// 泳池水面的横向范围
+const POOL_LEFT: f32 = -380.0;
+const POOL_RIGHT: f32 = 420.0;
+
+// 僵尸进出水路，在水中播放游泳动画、隐藏下半身并以水中速度移动
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_zombie_swim(
     mut commands: Commands,
-    mut zombies: Query<(&mut ZombieHp, Option<&InvincibleTag>), With<ZombieAttackableTag>>,
-    projectiles: Query<(Entity, &GlobalTransform), With<ProjectileTag>>,
-    zombie_kdtree: Res<KDTree2<ZombieAttackableTag>>,
-    asset_server: Res<AssetServer>,
+    zombie_solts: Query<(&ZombieSolt, &LanePosition)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut zombies: Query<
+        (
+            Entity,
+            &ZombieMetadata,
+            &LanePosition,
+            &mut MoveVelocity,
+            Option<&mut Spine>,
+            Option<&ZombieSwimTag>,
+            Has<AnimZombieMoveTag>,
+            Has<AnimZombieSwimTag>,
+        ),
+        (
+            With<ZombieTag>,
+            Without<ZombieFlyingTag>,
+            Without<ZombieJumpingTag>,
+            Without<ZombieCriticalTag>,
+        ),
+    >,
 ) {
-    for (projectile_entity, transform) in &projectiles {
-        // 投掷物位置
-        let projectile_translation = transform.translation();
-        let projectile_position = Vec2 {
-            x: projectile_translation.x,
-            y: projectile_translation.y,
-        };
+    // 水路
+    let river_lanes = zombie_solts
+        .iter()
+        .filter(|(solt, _)| matches!(solt.solt_type, SoltType::River))
+        .map(|(_, lane_position)| lane_position.lane)
+        .collect::<Vec<_>>();
+    if river_lanes.is_empty() {
+        return;
+    }
 
-        // 最近的僵尸
-        let Some((zombie_position, Some(zombie_entity))) =
-            zombie_kdtree.nearest_neighbour(projectile_position)
-        else {
+    for (
+        entity,
+        ZombieMetadata(metadata),
+        lane_position,
+        mut velocity,
+        spine,
+        swim_tag,
+        moving,
+        swim_anim,
+    ) in &mut zombies
+    {
+        let Some(swim) = &metadata.swim else {
             continue;
         };
+        let in_water = river_lanes.contains(&lane_position.lane)
+            && (POOL_LEFT..POOL_RIGHT).contains(&lane_position.x);
+
+        // 入水或上岸
+        let swimming = swim_tag.is_some();
+        if in_water != swimming {
+            velocity.0.x = match in_water {
+                true => -swim.speed,
+                false => -metadata.speed,
+            };
+            match swim_tag {
+                None => {
+                    // TODO: 僵尸骨骼中暂无游泳动画，先在水面显示波纹代替
+                    let ripple = commands
+                        .spawn(MaterialMesh2dBundle {
+                            mesh: meshes.add(Ellipse::new(35.0, 10.0)).into(),
+                            material: materials.add(Color::srgba(0.75, 0.9, 1.0, 0.6)),
+                            transform: Transform::from_xyz(0.0, 30.0, 1.0),
+                            ..Default::default()
+                        })
+                        .set_parent(entity)
+                        .id();
+                    commands.entity(entity).insert(ZombieSwimTag { ripple });
+                }
+                Some(swim_tag) => {
+                    commands.entity(swim_tag.ripple).despawn_recursive();
+                    commands.entity(entity).remove::<ZombieSwimTag>();
+                }
+            }
+        }
 
-        // 僵尸碰撞箱
-        let zombie_collision = Rect {
-            min: zombie_position - Vec2 { x: 31.0, y: 14.0 },
-            max: zombie_position + Vec2 { x: 15.0, y: 131.0 },
-        };
-
-        // 碰撞检测
-        if !zombie_collision.contains(projectile_position) {
-            continue;
+        // 游泳动画只在移动时播放，啃食结束后重新播放
+        match (in_water && moving, swim_anim) {
+            (true, false) => {
+                commands.entity(entity).insert(AnimZombieSwimTag);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<AnimZombieSwimTag>();
+            }
+            _ => {}
         }
 
-        // 僵尸信息
-        let Ok((mut zombie_hp, invincible)) = zombies.get_mut(zombie_entity) else {
+        // 无窗口模式下没有骨骼动画
+        let Some(mut spine) = spine else {
             continue;
         };
-
-        // 如果不处于无敌状态，则计算伤害
-        if invincible.is_none() {
-            zombie_hp.damage(20.0);
+        if !in_water && !swimming {
+            continue;
         }
 
-        // 音效
-        spawn_se(
-            &mut commands,
-            &asset_server,
-            *["sounds/splat.ogg", "sounds/splat2.ogg", "sounds/splat3.ogg"]
-                .choose(&mut thread_rng())
-                .unwrap(),
-        );
+        // 动画会重新设置插槽颜色，水中每帧都要隐藏下半身
+        let alpha = match in_water {
+            true => 0.0,
+            false => 1.0,
+        };
+        set_lower_body_alpha(&mut spine, swim, alpha);
+    }
+}
 
-        // 受击动画
-        commands.entity(zombie_entity).insert(AnimHitTag);
+// 濒死的僵尸停在水中，仍然需要隐藏下半身
+pub(crate) fn update_critical_zombie_swim(
+    mut zombies: Query<
+        (&ZombieMetadata, &mut Spine),
+        (With<ZombieSwimTag>, With<ZombieCriticalTag>),
+    >,
+) {
+    for (ZombieMetadata(metadata), mut spine) in &mut zombies {
+        let Some(swim) = &metadata.swim else {
+            continue;
+        };
+        set_lower_body_alpha(&mut spine, swim, 0.0);
+    }
+}
 
-        // 移除投掷物
-        commands.entity(projectile_entity).despawn_recursive();
+fn set_lower_body_alpha(spine: &mut Spine, swim: &Swim, alpha: f32) {
+    for name in &swim.lower_body {
+        if let Some(mut slot) = spine.skeleton.find_slot_mut(name) {
+            slot.color_mut().a = alpha;
+        }
+    }
+}
+
+// 僵尸结算投掷物伤害
+#[allow(clippy::type_complexity)]
+pub(crate) fn zombie_projectile_damage(
+    mut commands: Commands,
+    mut zombies: Query<
+        (
+            &mut ZombieHp,
+            &mut ZombieStatus,
+            &ZombieMetadata,
+            &Hitbox,
+            Has<ZombieFlyingTag>,
+            Option<&InvincibleTag>,
+        ),
+        With<ZombieAttackableTag>,
+    >,
+    mut projectiles: Query<(
+        Entity,
+        &mut ProjectileTag,
+        &GlobalTransform,
+        Option<&ThrowProjectileTag>,
+        Option<&LanePosition>,
+        Option<&MoveVelocity>,
+        Has<FollowProjectileTag>,
+    )>,
+    zombie_index: Res<LaneIndex<ZombieAttackableTag>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (projectile_entity, mut projectile, transform, throw, lane, velocity, follow) in
+        &mut projectiles
+    {
+        // 抛物线投掷物只在下落时结算
+        if throw.is_some() && velocity.is_some_and(|velocity| velocity.0.y > 0.0) {
+            continue;
+        }
+
+        // 投掷物位置
+        let projectile_translation = transform.translation();
+        let projectile_position = Vec2 {
+            x: projectile_translation.x,
+            y: projectile_translation.y,
+        };
+
+        // 附近的僵尸，有所在路的投掷物只与同一路的僵尸碰撞
+        let Some(zombie_entity) = zombie_index
+            .range(
+                lane.map(|lane| lane.lane),
+                projectile_position.x - ZOMBIE_SEARCH_RANGE,
+                projectile_position.x + ZOMBIE_SEARCH_RANGE,
+            )
+            .find_map(|(zombie_position, zombie_entity)| {
+                // 穿透时不重复命中
+                if projectile.hit_zombies.contains(&zombie_entity) {
+                    return None;
+                }
+
+                let (_, _, _, hitbox, flying, _) = zombies.get(zombie_entity).ok()?;
+
+                // 碰撞检测，水平飞行的投掷物只判断水平方向
+                let hitbox = hitbox.at(zombie_position);
+                if lane.is_some() && throw.is_none() {
+                    if !(hitbox.min.x..=hitbox.max.x).contains(&projectile_position.x) {
+                        return None;
+                    }
+                } else if !hitbox.contains(projectile_position) {
+                    return None;
+                }
+
+                // 飞行僵尸只能被追踪投掷物命中
+                if flying && !follow {
+                    return None;
+                }
+
+                Some(zombie_entity)
+            })
+        else {
+            continue;
+        };
+
+        // 僵尸信息
+        let Ok((mut zombie_hp, mut zombie_status, ZombieMetadata(metadata), _, _, invincible)) =
+            zombies.get_mut(zombie_entity)
+        else {
+            continue;
+        };
+        let info = projectile.info.clone();
+
+        // 如果不处于无敌状态，则计算伤害
+        // 抛物线投掷物从上方落下，无视前置护盾
+        if invincible.is_none() {
+            if throw.is_some() {
+                zombie_hp.damage_ignore_shield(info.damage, &metadata.hp);
+            } else {
+                zombie_hp.damage(info.damage);
+            }
+        }
+
+        // 状态效果
+        for effect in &info.effects {
+            zombie_status.apply(effect.effect_type, effect.duration, effect.strength);
+        }
+
+        // 溅射伤害
+        if let Some(splash) = &info.splash {
+            for (splash_position, splash_entity) in zombie_index.range(
+                None,
+                projectile_position.x - splash.radius,
+                projectile_position.x + splash.radius,
+            ) {
+                if splash_entity == zombie_entity
+                    || splash_position.distance_squared(projectile_position) > splash.radius.powi(2)
+                {
+                    continue;
+                }
+                let Ok((mut zombie_hp, mut zombie_status, ZombieMetadata(metadata), _, _, None)) =
+                    zombies.get_mut(splash_entity)
+                else {
+                    continue;
+                };
+
+                if throw.is_some() {
+                    zombie_hp.damage_ignore_shield(splash.damage, &metadata.hp);
+                } else {
+                    zombie_hp.damage(splash.damage);
+                }
+                for effect in &info.effects {
+                    zombie_status.apply(effect.effect_type, effect.duration, effect.strength);
+                }
+            }
+        }
+
+        // 音效
+        spawn_random_se(
+            &mut commands,
+            &asset_server,
+            &["sounds/splat.ogg", "sounds/splat2.ogg", "sounds/splat3.ogg"],
+        );
+
+        // 受击动画
+        commands.entity(zombie_entity).insert(AnimHitTag);
+
+        // 穿透
+        if projectile.pierce > 0 {
+            projectile.pierce -= 1;
+            projectile.hit_zombies.insert(zombie_entity);
+            continue;
+        }
+
+        // 移除投掷物
+        commands.entity(projectile_entity).despawn_recursive();
+    }
+}
+
+// 僵尸状态效果计时与灼烧伤害
+pub(crate) fn update_zombie_status(
+    time: Res<Time>,
+    mut zombies: Query<(&mut ZombieStatus, &mut ZombieHp)>,
+) {
+    let delta = time.delta();
+    zombies.par_iter_mut().for_each(|(mut status, mut hp)| {
+        // 计时不算作状态变化，只有效果增减时才需要重新染色
+        let effects = &mut status.bypass_change_detection().0;
+        for effect in effects.iter_mut() {
+            effect.timer.tick(delta);
+
+            // 灼烧伤害
+            if matches!(effect.effect_type, StatusEffectType::Burn) {
+                hp.damage(effect.strength * delta.as_secs_f32());
+            }
+        }
+        let count = effects.len();
+        effects.retain(|effect| !effect.timer.finished());
+        if effects.len() != count {
+            status.set_changed();
+        }
+    });
+}
+
+// 状态效果变化时更新染色与动画速度，临界状态的僵尸需要播放死亡动画，不受影响
+// 无窗口模式下没有骨骼动画
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_zombie_status_anim(
+    mut zombies: Query<
+        (&ZombieStatus, &mut Spine),
+        (Changed<ZombieStatus>, Without<ZombieCriticalTag>),
+    >,
+) {
+    for (status, mut spine) in &mut zombies {
+        // 染色
+        let tint = status.tint().to_srgba();
+        *spine.skeleton.color_mut() =
+            bevy_spine::rusty_spine::Color::new_rgba(tint.red, tint.green, tint.blue, tint.alpha);
+
+        // 动画速度
+        spine.animation_state.set_timescale(status.speed_factor());
+    }
+}
+
+// 进入临界状态时恢复染色与动画速度，冰冻的僵尸也能播放死亡动画
+pub(crate) fn reset_critical_zombie_anim(
+    mut zombies: Query<&mut Spine, (With<ZombieTag>, Added<ZombieCriticalTag>)>,
+) {
+    for mut spine in &mut zombies {
+        *spine.skeleton.color_mut() = bevy_spine::rusty_spine::Color::new_rgba(1.0, 1.0, 1.0, 1.0);
+        spine.animation_state.set_timescale(1.0);
+    }
+}
+
+// 追踪投掷物转向目标
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_follow_projectile(
+    time: Res<Time>,
+    mut projectiles: Query<
+        (
+            &mut FollowProjectileTag,
+            &mut MoveVelocity,
+            &mut Transform,
+            &GlobalTransform,
+        ),
+        Without<Freeze>,
+    >,
+    zombies: Query<&GlobalTransform, (With<ZombieAttackableTag>, Without<ZombieCriticalTag>)>,
+    zombie_index: Res<LaneIndex<ZombieAttackableTag>>,
+) {
+    let delta = time.delta().as_secs_f32();
+    for (mut follow, mut velocity, mut transform, global_transform) in &mut projectiles {
+        // 投掷物位置
+        let translation = global_transform.translation();
+        let position = Vec2 {
+            x: translation.x,
+            y: translation.y,
+        };
+
+        // 目标死亡或进入临界状态，重新寻找目标
+        if !follow.target.is_some_and(|target| zombies.contains(target)) {
+            follow.target = zombie_index
+                .iter()
+                .filter(|(_, entity)| zombies.contains(*entity))
+                .min_by(|(a, _), (b, _)| {
+                    a.distance_squared(position)
+                        .total_cmp(&b.distance_squared(position))
+                })
+                .map(|(_, entity)| entity);
+        }
+        let Some(target_transform) = follow.target.and_then(|target| zombies.get(target).ok())
+        else {
+            continue;
+        };
+
+        // 瞄准僵尸身体
+        let target_translation = target_transform.translation();
+        let target_position = Vec2 {
+            x: target_translation.x,
+            y: target_translation.y + FOLLOW_TARGET_HEIGHT,
+        };
+
+        // 按转向速度限制，向目标方向偏转
+        let speed = velocity.0.length();
+        let current_angle = velocity.0.y.atan2(velocity.0.x);
+        let offset = target_position - position;
+        let target_angle = offset.y.atan2(offset.x);
+        let diff = (target_angle - current_angle + PI).rem_euclid(TAU) - PI;
+        let max_turn = follow.turn_rate * delta;
+        let angle = current_angle + diff.clamp(-max_turn, max_turn);
+
+        velocity.0 = Vec2::from_angle(angle) * speed;
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+// 抛物线投掷物未命中，落地后移除
+pub(crate) fn update_throw_projectile_land(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &GlobalTransform, &ThrowProjectileTag, &MoveVelocity)>,
+) {
+    for (entity, transform, throw, velocity) in &projectiles {
+        if velocity.0.y > 0.0 || transform.translation().y > throw.land_y - THROW_TARGET_HEIGHT {
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
     }
 }
 
@@ -1822,6 +2534,75 @@ pub(crate) fn update_zombie_die(
     }
 }
 
+// 僵尸掉落金币的几率与面值
+const SILVER_COIN_CHANCE: f32 = 0.1;
+const SILVER_COIN_VALUE: u32 = 10;
+const GOLD_COIN_CHANCE: f32 = 0.02;
+const GOLD_COIN_VALUE: u32 = 50;
+
+// 僵尸死亡时有几率掉落金币，一段时间后消失
+pub(crate) fn drop_zombie_coin(
+    mut commands: Commands,
+    zombies: Query<&GlobalTransform, (With<ZombieTag>, Added<ToDespawn>)>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for transform in &zombies {
+        let roll: f32 = rng.gen();
+        let (value, color) = if roll < GOLD_COIN_CHANCE {
+            (GOLD_COIN_VALUE, Color::srgb(0.95, 0.78, 0.2))
+        } else if roll < GOLD_COIN_CHANCE + SILVER_COIN_CHANCE {
+            (SILVER_COIN_VALUE, Color::srgb(0.8, 0.8, 0.85))
+        } else {
+            continue;
+        };
+
+        let translation = transform.translation();
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(12.0)).into(),
+                material: materials.add(color),
+                transform: Transform::from_xyz(translation.x, translation.y + 40.0, 30.0),
+                ..Default::default()
+            },
+            CoinTag { value },
+            ToDespawn(Timer::from_seconds(10.0, TimerMode::Once)),
+            SceneTag,
+        ));
+    }
+}
+
+// 点击收集金币
+pub(crate) fn collect_coin(
+    mut commands: Commands,
+    coins: Query<(Entity, &CoinTag, &GlobalTransform)>,
+    cursor_position: Res<CursorPosition>,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    asset_server: Res<AssetServer>,
+    mut userdata: ResMut<UserData>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some((entity, coin, _)) = coins.iter().find(|(_, _, transform)| {
+        transform
+            .translation()
+            .truncate()
+            .distance_squared(cursor_position.world_position)
+            <= 30.0 * 30.0
+    }) else {
+        return;
+    };
+
+    mouse_button_input.clear_just_pressed(MouseButton::Left);
+    commands.entity(entity).despawn_recursive();
+    userdata.coins += coin.value;
+
+    spawn_se(&mut commands, &asset_server, "sounds/points.ogg");
+}
+
 // 延迟移除实体
 pub(crate) fn despawn_schedule_entity(
     mut commands: Commands,
@@ -1854,22 +2635,34 @@ pub(crate) fn update_plant_shoot_enable(
         &GlobalTransform,
     )>,
     zombies: Query<(&LanePosition, &GlobalTransform), With<ZombieAttackableTag>>,
+    zombie_index: Res<LaneIndex<ZombieAttackableTag>>,
+    target_index: Res<LaneIndex<VersusTarget>>,
 ) {
     plants
         .par_iter_mut()
         .for_each(|(plant, mut shoot, plant_position, plant_transform)| {
+            // 对战模式中，同一路的目标同样视为敌人
             let found_enemy = match plant.metadata.shoot.as_ref().map(|shot| &shot.detect) {
-                Some(PlantDetect::LaneFront) => zombies.iter().any(|(zombie_position, _)| {
-                    zombie_position.lane == plant_position.lane
-                        && zombie_position.x >= plant_position.x
-                }),
-                Some(PlantDetect::LaneBack) => zombies.iter().any(|(zombie_position, _)| {
-                    zombie_position.lane == plant_position.lane
-                        && zombie_position.x <= plant_position.x
-                }),
-                Some(PlantDetect::Lane) => zombies
-                    .iter()
-                    .any(|(zombie_position, _)| zombie_position.lane == plant_position.lane),
+                Some(PlantDetect::LaneFront) => {
+                    zombie_index
+                        .first_ahead(plant_position.lane, plant_position.x)
+                        .is_some()
+                        || target_index
+                            .first_ahead(plant_position.lane, plant_position.x)
+                            .is_some()
+                }
+                Some(PlantDetect::LaneBack) => {
+                    zombie_index
+                        .first_behind(plant_position.lane, plant_position.x)
+                        .is_some()
+                        || target_index
+                            .first_behind(plant_position.lane, plant_position.x)
+                            .is_some()
+                }
+                Some(PlantDetect::Lane) => {
+                    !zombie_index.lane(plant_position.lane).is_empty()
+                        || !target_index.lane(plant_position.lane).is_empty()
+                }
                 Some(PlantDetect::Rays { direction }) => {
                     let plant_translation = plant_transform.translation();
                     let plant_origin = Vec2 {
@@ -1948,7 +2741,6 @@ pub(crate) fn update_zombie_wave(
     mut commands: Commands,
     time: Res<Time>,
     mut zombie_wave_controller: ResMut<ZombieWaveController>,
-    current_level: Res<CurrentLevel>,
     zombies: Query<
         (),
         Or<(
@@ -1958,8 +2750,9 @@ pub(crate) fn update_zombie_wave(
     >,
     zombie_registry: Res<ZombieRegistry>,
     zombie_solts: Query<(Entity, &ZombieSolt)>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if zombie_wave_controller.next_wave_index >= current_level.waves.len() {
+    if zombie_wave_controller.next_wave_index >= zombie_wave_controller.waves.len() {
         return;
     }
 
@@ -1984,7 +2777,7 @@ pub(crate) fn update_zombie_wave(
 
     // 一大波僵尸正在接近！
     if !zombie_wave_controller.trigger_huge_wave
-        && current_level
+        && zombie_wave_controller
             .waves
             .get(zombie_wave_controller.next_wave_index)
             .map(|wave| matches!(wave.wave_type, WaveType::HugeWave))
@@ -2030,9 +2823,12 @@ pub(crate) fn update_zombie_wave(
         ));
     }
 
-    // 最后一波
-    if zombie_wave_controller.next_wave_index == current_level.waves.len() - 1 {
+    // 最后一波，生存模式只在最后一轮提示
+    if zombie_wave_controller.final_round
+        && zombie_wave_controller.next_wave_index == zombie_wave_controller.waves.len() - 1
+    {
         debug!("wave controller: final wave");
+        zombie_wave_controller.summon_from_graves = true;
         commands.spawn((
             GameTimer(Timer::from_seconds(0.0, TimerMode::Once)),
             GameTimerTag::TextFinalWave,
@@ -2046,7 +2842,7 @@ pub(crate) fn update_zombie_wave(
     }
 
     // 波次数据
-    let Some(wave) = current_level
+    let Some(wave) = zombie_wave_controller
         .waves
         .get(zombie_wave_controller.next_wave_index)
     else {
@@ -2055,7 +2851,6 @@ pub(crate) fn update_zombie_wave(
 
     // 生成僵尸
     debug!("wave controller: summon zombies: {:?}", wave.zombies);
-    let mut rng = thread_rng();
     for Zombie { zombie, count } in &wave.zombies {
         for _ in 0..*count {
             // 僵尸信息
@@ -2070,7 +2865,7 @@ pub(crate) fn update_zombie_wave(
                 })
                 .collect::<Vec<_>>();
             // 随机一个生成僵尸的点位
-            let Some((entity, _)) = solts.choose(&mut rng) else {
+            let Some((entity, _)) = solts.choose(&mut *rng) else {
                 break;
             };
 
@@ -2093,6 +2888,10 @@ pub(crate) fn update_zombie_wave(
         }
     }
 
+    // 本波的墓碑
+    let graves = wave.graves;
+    zombie_wave_controller.pending_graves += graves;
+
     // 下一波
     zombie_wave_controller.next_wave_index += 1;
 
@@ -2101,15 +2900,170 @@ pub(crate) fn update_zombie_wave(
         Timer::new(Duration::from_secs_f32(30.0), TimerMode::Once);
 }
 
+// 在右侧随机的空格子中生成墓碑
+pub(crate) fn place_graves(
+    commands: &mut Commands,
+    solts: &mut Query<(&mut PlantSolt, &GlobalTransform, &LanePosition)>,
+    count: u32,
+    min_column: u8,
+    rng: &mut GameRng,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    let candidates = solts
+        .iter_mut()
+        .filter(|(solt, transform, _)| {
+            matches!(solt.solt_type, SoltType::Dirt)
+                && solt.grave.is_none()
+                && solt.hole.is_none()
+                && solt.plants().iter().all(Option::is_none)
+                && versus::cell_column(transform.translation().x) >= min_column
+        })
+        .collect::<Vec<_>>();
+
+    for (mut solt, transform, lane_position) in candidates
+        .into_iter()
+        .choose_multiple(&mut *rng, count as usize)
+    {
+        let translation = transform.translation();
+        // TODO: 暂无墓碑的贴图，先用灰色矩形代替
+        let grave = commands
+            .spawn((
+                MaterialMesh2dBundle {
+                    mesh: meshes.add(Rectangle::new(50.0, 60.0)).into(),
+                    material: materials.add(Color::srgb(0.45, 0.45, 0.5)),
+                    transform: Transform::from_xyz(translation.x, translation.y + 10.0, 9.0),
+                    ..Default::default()
+                },
+                GraveTag,
+                LanePosition {
+                    lane: lane_position.lane,
+                    x: translation.x,
+                },
+                SceneTag,
+            ))
+            .id();
+        solt.grave = Some(grave);
+    }
+}
+
+// 生成本波的墓碑
+pub(crate) fn spawn_wave_graves(
+    mut commands: Commands,
+    mut zombie_wave_controller: ResMut<ZombieWaveController>,
+    mut solts: Query<(&mut PlantSolt, &GlobalTransform, &LanePosition)>,
+    current_level: Res<CurrentLevel>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if zombie_wave_controller.pending_graves == 0 {
+        return;
+    }
+
+    place_graves(
+        &mut commands,
+        &mut solts,
+        zombie_wave_controller.pending_graves,
+        current_level.graves.min_column,
+        &mut rng,
+        &mut meshes,
+        &mut materials,
+    );
+    zombie_wave_controller.pending_graves = 0;
+}
+
+// 最后一波时，每个墓碑中生成一只僵尸，种类取自最后一波的僵尸
+pub(crate) fn summon_grave_zombies(
+    mut commands: Commands,
+    mut zombie_wave_controller: ResMut<ZombieWaveController>,
+    graves: Query<Entity, With<GraveTag>>,
+    mut rng: ResMut<GameRng>,
+) {
+    if !zombie_wave_controller.summon_from_graves {
+        return;
+    }
+    zombie_wave_controller.summon_from_graves = false;
+
+    let zombies = zombie_wave_controller
+        .waves
+        .last()
+        .map(|wave| {
+            wave.zombies
+                .iter()
+                .map(|zombie| zombie.zombie)
+                .filter(|zombie| *zombie != ZombieType::Flag)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for grave in &graves {
+        let zombie_type = zombies
+            .choose(&mut *rng)
+            .copied()
+            .unwrap_or(ZombieType::Basic);
+        commands.spawn((
+            ToSpawnZombie {
+                timer: Timer::new(
+                    Duration::from_secs_f32(rng.gen_range(0.0..2.0)),
+                    TimerMode::Once,
+                ),
+                zombie_type,
+                zombie_solt: grave,
+            },
+            SceneTag,
+        ));
+    }
+}
+
+// 种下墓碑吞噬者后开始计时
+pub(crate) fn start_consume_grave(
+    mut commands: Commands,
+    plants: Query<(Entity, &PlantTag), Added<PlantTag>>,
+) {
+    for (entity, PlantTag { metadata, .. }) in &plants {
+        let Some(consume_time) = metadata.consume_grave else {
+            continue;
+        };
+
+        commands.entity(entity).insert(PlantConsumeGraveTag {
+            timer: Timer::new(Duration::from_secs_f32(consume_time), TimerMode::Once),
+        });
+    }
+}
+
+// 吞掉所在格子的墓碑，植物随后消失
+pub(crate) fn update_consume_grave(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut plants: Query<(&mut PlantConsumeGraveTag, &mut PlantHp, &PlantTag)>,
+    mut solts: Query<&mut PlantSolt>,
+) {
+    for (mut consume, mut hp, plant) in &mut plants {
+        if !consume.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        // 由 update_plant_die 移除
+        hp.0 = 0.0;
+
+        let Ok(mut solt) = solts.get_mut(plant.solt) else {
+            continue;
+        };
+        if let Some(grave) = solt.grave.take() {
+            commands.entity(grave).despawn_recursive();
+        }
+    }
+}
+
 // 更新关卡进度条
 pub(crate) fn update_level_progress(
-    current_level: Res<CurrentLevel>,
     zombie_wave_controller: Res<ZombieWaveController>,
     mut level_progress: Query<&mut Sprite, With<LevelProgressProgressTag>>,
     time: Res<Time>,
 ) {
     // 旗帜数量
-    let flag_count = current_level
+    let flag_count = zombie_wave_controller
         .waves
         .iter()
         .filter(|wave| matches!(wave.wave_type, WaveType::HugeWave))
@@ -2121,7 +3075,10 @@ pub(crate) fn update_level_progress(
     let huge_wave = {
         let mut count = 0;
         for i in 0..zombie_wave_controller.next_wave_index {
-            if matches!(current_level.waves[i].wave_type, WaveType::HugeWave) {
+            if matches!(
+                zombie_wave_controller.waves[i].wave_type,
+                WaveType::HugeWave
+            ) {
                 count += 1;
             }
         }
@@ -2131,7 +3088,10 @@ pub(crate) fn update_level_progress(
         let mut count = 0;
         for i in 0..zombie_wave_controller.next_wave_index {
             let i = zombie_wave_controller.next_wave_index - i - 1;
-            if matches!(current_level.waves[i].wave_type, WaveType::HugeWave) {
+            if matches!(
+                zombie_wave_controller.waves[i].wave_type,
+                WaveType::HugeWave
+            ) {
                 break;
             }
             count += 1;
@@ -2140,8 +3100,11 @@ pub(crate) fn update_level_progress(
     };
     let wave_before_huge = {
         let mut count = 0;
-        for i in zombie_wave_controller.next_wave_index..current_level.waves.len() {
-            if matches!(current_level.waves[i].wave_type, WaveType::HugeWave) {
+        for i in zombie_wave_controller.next_wave_index..zombie_wave_controller.waves.len() {
+            if matches!(
+                zombie_wave_controller.waves[i].wave_type,
+                WaveType::HugeWave
+            ) {
                 break;
             }
             count += 1;
@@ -2156,7 +3119,7 @@ pub(crate) fn update_level_progress(
     progress = progress.clamp(0., 158.);
 
     // special case: 没有huge wave时，最后一波展示会有问题，特殊处理下
-    if zombie_wave_controller.next_wave_index == current_level.waves.len() {
+    if zombie_wave_controller.next_wave_index == zombie_wave_controller.waves.len() {
         progress = 158.;
     }
 
@@ -2247,16 +3210,16 @@ pub(crate) fn update_level_progress_flag(
 // 僵尸吃植物 伤害结算
 pub(crate) fn update_zombie_eat(
     time: Res<Time>,
-    zombies: Query<(&ZombieMetadata, &ZombieEatTag), Without<ZombieCriticalTag>>,
+    zombies: Query<(&ZombieMetadata, &ZombieEatTag, &ZombieStatus), Without<ZombieCriticalTag>>,
     mut plants: Query<&mut PlantHp, (With<PlantTag>, Without<InvincibleTag>)>,
 ) {
     let delta = time.delta().as_secs_f32();
-    for (ZombieMetadata(metadata), eat_tag) in &zombies {
+    for (ZombieMetadata(metadata), eat_tag, status) in &zombies {
         let Ok(mut plant_hp) = plants.get_mut(eat_tag.target_plant) else {
             continue;
         };
 
-        plant_hp.0 -= metadata.attack * delta;
+        plant_hp.0 -= metadata.attack * delta * status.eat_factor();
     }
 }
 
@@ -2290,28 +3253,119 @@ pub(crate) fn update_plant_die(
     }
 }
 
-// 僵尸啃食逻辑
-pub(crate) fn update_zombie_eat_timer(
+// 咖啡豆唤醒植物需要的时间
+const WAKE_UP_TIME: f32 = 2.0;
+
+// 白天种下的夜行植物会睡着
+pub(crate) fn sleep_nocturnal_plants(
     mut commands: Commands,
-    time: Res<Time>,
-    mut zombies: Query<&mut ZombieEatTag, Without<ZombieCriticalTag>>,
+    plants: Query<(Entity, &PlantTag), Added<PlantTag>>,
+    current_level: Res<CurrentLevel>,
     asset_server: Res<AssetServer>,
 ) {
-    let mut rng = thread_rng();
-    for mut eat_tag in &mut zombies {
-        // 计时器
-        eat_tag.timer.tick(time.delta());
-        if !eat_tag.timer.just_finished() {
-            continue;
-        }
+    if current_level.background.is_night() {
+        return;
+    }
+
+    for (entity, PlantTag { metadata, .. }) in &plants {
+        if !metadata.nocturnal {
+            continue;
+        }
+
+        // TODO: 植物骨骼中暂无睡眠动画，先在头顶显示 Zzz 代替
+        let marker = commands
+            .spawn(Text2dBundle {
+                text: Text::from_section(
+                    "Zzz",
+                    TextStyle {
+                        font: asset_server.load("font/fzcgbk.ttf"),
+                        font_size: 20.0,
+                        color: Color::srgb(0.85, 0.9, 1.0),
+                    },
+                ),
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(20.0, 70.0, 1.0),
+                ..Default::default()
+            })
+            .set_parent(entity)
+            .id();
+
+        commands
+            .entity(entity)
+            .remove::<AnimStandbyTag>()
+            .insert((PlantSleepTag { marker }, AnimPlantSleepTag));
+    }
+}
+
+// 种下临时植物后开始计时
+pub(crate) fn start_wake_up(
+    mut commands: Commands,
+    plants: Query<(Entity, &PlantTag), Added<PlantTag>>,
+) {
+    for (entity, PlantTag { metadata, .. }) in &plants {
+        if !matches!(metadata.position, PlantPosition::Temp) {
+            continue;
+        }
+
+        commands.entity(entity).insert(PlantWakeUpTag {
+            timer: Timer::new(Duration::from_secs_f32(WAKE_UP_TIME), TimerMode::Once),
+        });
+    }
+}
+
+// 唤醒同一格子中睡着的植物，临时植物随后消失
+pub(crate) fn update_wake_up(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut temp_plants: Query<(&mut PlantWakeUpTag, &mut PlantHp, &PlantTag)>,
+    solts: Query<&PlantSolt>,
+    sleeping_plants: Query<&PlantSleepTag>,
+) {
+    for (mut wake_up, mut hp, plant) in &mut temp_plants {
+        if !wake_up.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        // 由 update_plant_die 移除
+        hp.0 = 0.0;
+
+        let Ok(solt) = solts.get(plant.solt) else {
+            continue;
+        };
+        for entity in solt.plants().into_iter().flatten() {
+            let Ok(sleep) = sleeping_plants.get(entity) else {
+                continue;
+            };
+            commands.entity(sleep.marker).despawn_recursive();
+            commands
+                .entity(entity)
+                .remove::<(PlantSleepTag, AnimPlantSleepTag)>()
+                .insert(AnimStandbyTag);
+        }
+    }
+}
+
+// 僵尸啃食逻辑
+pub(crate) fn update_zombie_eat_timer(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut zombies: Query<(&mut ZombieEatTag, &ZombieStatus), Without<ZombieCriticalTag>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (mut eat_tag, status) in &mut zombies {
+        // 计时器
+        eat_tag
+            .timer
+            .tick(time.delta().mul_f32(status.eat_factor()));
+        if !eat_tag.timer.just_finished() {
+            continue;
+        }
 
         // 播放音效
-        spawn_se(
+        spawn_random_se(
             &mut commands,
             &asset_server,
-            *["sounds/chomp.ogg", "sounds/chomp2.ogg"]
-                .choose(&mut rng)
-                .unwrap(),
+            &["sounds/chomp.ogg", "sounds/chomp2.ogg"],
         );
 
         // 植物受击闪烁
@@ -2321,13 +3375,27 @@ pub(crate) fn update_zombie_eat_timer(
     }
 }
 
+// 记录遇到过的僵尸，包括开局时预览的僵尸，用于图鉴
+pub(crate) fn record_seen_zombies(
+    zombies: Query<&ZombieMetadata, Added<ZombieMetadata>>,
+    mut userdata: ResMut<UserData>,
+) {
+    for ZombieMetadata(metadata) in &zombies {
+        // 避免每帧都触发存档
+        if userdata.seen_zombies.contains(&metadata.id) {
+            continue;
+        }
+        userdata.seen_zombies.insert(metadata.id);
+    }
+}
+
 // 延迟生成僵尸
 pub(crate) fn update_summon_zombie(
     mut commands: Commands,
     mut to_spawn_zombie: Query<(Entity, &mut ToSpawnZombie)>,
     time: Res<Time>,
     zombie_registry: Res<ZombieRegistry>,
-    zombie_solts: Query<(&LanePosition, &GlobalTransform), With<ZombieSolt>>,
+    zombie_solts: Query<(&LanePosition, &GlobalTransform), Or<(With<ZombieSolt>, With<GraveTag>)>>,
 ) {
     for (entity, mut to_spawn_zombie) in &mut to_spawn_zombie {
         // 计时器
@@ -2344,36 +3412,68 @@ pub(crate) fn update_summon_zombie(
             continue;
         };
 
-        // 查找生成点
+        // 查找生成点，墓碑被吞掉时不再生成
         let Ok((lane_position, global_transform)) = zombie_solts.get(to_spawn_zombie.zombie_solt)
         else {
             continue;
         };
 
-        // 生成位置
-        let translation = global_transform.translation();
-
         // 生成
-        let mut zombie_bundle = ZombieBundle::new(zombie_info.clone());
-        zombie_bundle.spine.transform =
-            Transform::from_xyz(translation.x, translation.y, 15.0 - translation.y * 0.001);
-        commands.spawn((
-            zombie_bundle,
-            AnimStandbyTag,
-            AnimZombieMoveTag,
-            SceneTag,
-            ZombieTag,
-            ZombieAttackableTag,
-            MoveVelocity(Vec2 {
-                x: -zombie_info.speed,
-                y: 0.0,
-            }),
-            ZombieHpAnim::default(),
-            LanePosition {
-                lane: lane_position.lane,
-                x: translation.x,
+        spawn_zombie(
+            &mut commands,
+            zombie_info,
+            global_transform.translation(),
+            lane_position.lane,
+        );
+    }
+}
+
+// 在指定位置生成僵尸
+pub(crate) fn spawn_zombie(
+    commands: &mut Commands,
+    zombie_info: &Arc<ZombieInfo>,
+    translation: Vec3,
+    lane: u8,
+) {
+    let mut zombie_bundle = ZombieBundle::new(zombie_info.clone());
+    zombie_bundle.spine.transform =
+        Transform::from_xyz(translation.x, translation.y, 15.0 - translation.y * 0.001);
+    let mut zombie_entity = commands.spawn((
+        zombie_bundle,
+        AnimStandbyTag,
+        AnimZombieMoveTag,
+        SceneTag,
+        ZombieTag,
+        ZombieAttackableTag,
+        MoveVelocity(Vec2 {
+            x: -zombie_info.speed,
+            y: 0.0,
+        }),
+        ZombieHpAnim::default(),
+        ZombieStatus::default(),
+        Hitbox(Rect {
+            min: Vec2 {
+                x: zombie_info.hitbox.left,
+                y: zombie_info.hitbox.bottom,
             },
-        ));
+            max: Vec2 {
+                x: zombie_info.hitbox.right,
+                y: zombie_info.hitbox.top,
+            },
+        }),
+        LanePosition {
+            lane,
+            x: translation.x,
+        },
+    ));
+
+    // 飞行僵尸
+    if zombie_info.flying {
+        zombie_entity.insert(ZombieFlyingTag);
+    }
+    // 可以跳跃的僵尸
+    if let Some(jump) = &zombie_info.jump {
+        zombie_entity.insert(ZombieJumpTag { times: jump.times });
     }
 }
 
@@ -2418,6 +3518,7 @@ pub(crate) fn update_natural_sunshine(
     asset_server: Res<AssetServer>,
     mut skeletions: ResMut<Assets<SkeletonData>>,
     mut sunshine_skeleton: Local<Option<Handle<SkeletonData>>>,
+    mut rng: ResMut<GameRng>,
 ) {
     if sunshine_skeleton.is_none() {
         *sunshine_skeleton = Some(skeletions.add(SkeletonData::new_from_binary(
@@ -2429,8 +3530,6 @@ pub(crate) fn update_natural_sunshine(
         return;
     };
 
-    let mut rng = thread_rng();
-
     for mut solt in &mut sunshine_solt {
         solt.next_sunshine_timer.tick(time.delta());
 
@@ -2462,12 +3561,13 @@ pub(crate) fn update_natural_sunshine(
 }
 
 // 收集阳光
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn collect_sunshine(
     mut commands: Commands,
     mut sunshine: ResMut<Sunshine>,
     mut sunshines: Query<(
         Entity,
-        &mut Spine,
+        Option<&mut Spine>,
         &SunshineTag,
         &Transform,
         &GlobalTransform,
@@ -2475,13 +3575,16 @@ pub(crate) fn collect_sunshine(
     cursor_position: Res<CursorPosition>,
     mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
     asset_server: Res<AssetServer>,
+    mut actions: EventWriter<PlayerAction>,
+    session: Option<Res<NetSession>>,
+    mut versus_commands: EventWriter<VersusCommand>,
 ) {
     if !mouse_button_input.just_pressed(MouseButton::Left) {
         return;
     }
 
     // 点击的阳光
-    let Some((sunshine_entity, mut spine, sunshine_tag, transform)) = sunshines
+    let Some((sunshine_entity, spine, sunshine_tag, transform, position)) = sunshines
         .iter_mut()
         .filter_map(|(entity, spine, sunshine, transform, global_transform)| {
             let translation = global_transform.translation();
@@ -2494,7 +3597,7 @@ pub(crate) fn collect_sunshine(
             if distance_square > 55.0 * 55.0 {
                 return None;
             }
-            Some((entity, spine, sunshine, transform))
+            Some((entity, spine, sunshine, transform, position))
         })
         .next()
     else {
@@ -2503,7 +3606,41 @@ pub(crate) fn collect_sunshine(
 
     // 如果点到了阳光，清除点击事件
     mouse_button_input.clear_just_pressed(MouseButton::Left);
+    actions.send(PlayerAction::CollectSunshine {
+        x: position.x,
+        y: position.y,
+    });
+
+    // 联机时只发送操作，在双方的同一帧收集
+    if session.is_some() {
+        versus_commands.send(VersusCommand::CollectSunshine {
+            x: position.x,
+            y: position.y,
+        });
+        return;
+    }
+
+    collect_sunshine_entity(
+        &mut commands,
+        &asset_server,
+        &mut sunshine,
+        sunshine_entity,
+        spine,
+        sunshine_tag,
+        transform,
+    );
+}
 
+// 收集阳光，动画移动到左上角
+pub(crate) fn collect_sunshine_entity(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    sunshine: &mut Sunshine,
+    sunshine_entity: Entity,
+    spine: Option<Mut<Spine>>,
+    sunshine_tag: &SunshineTag,
+    transform: &Transform,
+) {
     // 移除阳光标记，动画移动到左上角
     let target_transform = Transform::from_xyz(-370. + 40., 300. - 35., transform.translation.z);
     commands
@@ -2529,13 +3666,15 @@ pub(crate) fn collect_sunshine(
         });
 
     // 消失淡出动画
-    play_anim!(spine, INDEX_SUN_FADE_OUT, NAME_SUN_FADE_OUT, false, 0.0);
+    if let Some(mut spine) = spine {
+        play_anim!(spine, INDEX_SUN_FADE_OUT, NAME_SUN_FADE_OUT, false, 0.0);
+    }
 
     // 阳光记数
     sunshine.0 += sunshine_tag.count;
 
     // 音效
-    spawn_se(&mut commands, &asset_server, "sounds/points.ogg");
+    spawn_se(commands, asset_server, "sounds/points.ogg");
 }
 
 // 触发清理车
@@ -2625,6 +3764,7 @@ pub(crate) fn update_reward_solt(
 }
 
 // 检查并生成关卡奖励
+#[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub(crate) fn check_summon_reward(
     mut commands: Commands,
@@ -2641,14 +3781,18 @@ pub(crate) fn check_summon_reward(
     asset_server: Res<AssetServer>,
     plant_registry: Res<PlantRegistry>,
     item_registry: Res<ItemRegistry>,
+    note_registry: Res<NoteRegistry>,
+    mut rng: ResMut<GameRng>,
 ) {
     // 必须存在奖励槽
     let Some((reward_solt_entity, reward_solt_transform)) = reward_solt.iter().next() else {
         return;
     };
 
-    // 所有波次必须均已释放
-    if zombie_wave_controller.next_wave_index < current_level.waves.len() {
+    // 所有波次必须均已释放，生存模式需要在最后一轮
+    if !zombie_wave_controller.final_round
+        || zombie_wave_controller.next_wave_index < zombie_wave_controller.waves.len()
+    {
         return;
     }
 
@@ -2674,7 +3818,6 @@ pub(crate) fn check_summon_reward(
         y: reward_start_translation.y.clamp(-250.0, 250.0),
     };
     // 随机偏移
-    let mut rng = thread_rng();
     safe_reward_translation.x += rng.gen_range(-20.0..20.0);
     safe_reward_translation.y += rng.gen_range(-20.0..20.0);
     // 计算初始速度
@@ -2725,7 +3868,8 @@ pub(crate) fn check_summon_reward(
         Some(Reward::Note { note_path }) => commands
             .spawn((
                 SpriteBundle {
-                    texture: asset_server.load(note_path.clone()),
+                    texture: asset_server
+                        .load(note_registry.get(note_path).unwrap().texture.clone()),
                     transform: Transform::from_translation(Vec3 {
                         x: reward_start_translation.x,
                         y: reward_start_translation.y,
@@ -2744,6 +3888,128 @@ pub(crate) fn check_summon_reward(
     };
 }
 
+// 生存模式：一轮结束后重新选卡，植物保留在草地上
+// 生存模式不录制录像，见 start_replay
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn check_survival_round(
+    mut commands: Commands,
+    mut zombie_wave_controller: ResMut<ZombieWaveController>,
+    current_level: Res<CurrentLevel>,
+    zombies: Query<
+        (),
+        Or<(
+            (With<ZombieAttackableTag>, Without<ZombieCriticalTag>),
+            With<ToSpawnZombie>,
+        )>,
+    >,
+    seeds: Query<Entity, (With<PlantSeed>, With<SeedbankTag>)>,
+    picked: Query<Entity, Or<(With<PickSeed>, With<PickShovel>)>>,
+    level_progress: Query<Entity, With<LevelProgressTag>>,
+    mut seed_chooser: Query<&mut Transform, With<SeedChooserTag>>,
+    mut shift_left: Query<
+        &mut Transform,
+        (With<ShowLevelProgressShiftLeft>, Without<SeedChooserTag>),
+    >,
+    bgm: Query<Entity, With<FtxmAudioSink>>,
+    mut userdata: ResMut<UserData>,
+    mut rng: ResMut<GameRng>,
+) {
+    let Some(survival) = &current_level.survival else {
+        return;
+    };
+
+    // 所有波次必须均已释放，且不是最后一轮
+    if zombie_wave_controller.final_round
+        || zombie_wave_controller.next_wave_index < zombie_wave_controller.waves.len()
+    {
+        return;
+    }
+
+    // 必须无存活僵尸
+    if !zombies.is_empty() {
+        return;
+    }
+
+    let round = zombie_wave_controller.round + 1;
+    debug!("survival: round {round} start");
+
+    // 无尽模式，记录最高旗数
+    if survival.endless && round > userdata.survival_endless_best {
+        userdata.survival_endless_best = round;
+    }
+
+    zombie_wave_controller.start_round(&current_level, round, &mut *rng);
+
+    // 进度条在下一轮第一波时重新显示
+    for entity in &level_progress {
+        commands.entity(entity).despawn_recursive();
+    }
+    for mut transform in &mut shift_left {
+        transform.translation.x += 175.0;
+    }
+
+    // 放下拿起的种子与铲子，进入战场前不可种植
+    for entity in &picked {
+        commands.entity(entity).despawn_recursive();
+    }
+    for entity in &seeds {
+        commands.entity(entity).remove::<PickableSeed>();
+    }
+
+    // 选卡界面在开局时移出了画面，移回原位以便再次显示
+    for mut transform in &mut seed_chooser {
+        transform.translation.y += 600.0;
+    }
+
+    // 切换到选卡音乐
+    for entity in &bgm {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.spawn((
+        FtxmSource {
+            pot: MainMusicTable::ChooseYourSeeds.into(),
+        },
+        SceneTag,
+    ));
+
+    // 摄像机移动到右侧，之后重新选卡，无需选卡时直接进入战场
+    commands.spawn((
+        GameTimer(Timer::from_seconds(0.0, TimerMode::Once)),
+        GameTimerTag::CameraToRightAnim,
+        SceneTag,
+    ));
+    let state = match userdata.unlock_plugins.len() <= userdata.plant_solt_count
+        || current_level.conveyor_belt.is_some()
+    {
+        true => GameState::Enter,
+        false => GameState::ChooseSeed,
+    };
+    commands.spawn((
+        GameTimer(Timer::from_seconds(2.0, TimerMode::Once)),
+        GameTimerTag::EnterState(state),
+        SceneTag,
+    ));
+}
+
+// 僵尸跳跃时越过的距离与耗时
+const ZOMBIE_JUMP_DISTANCE: f32 = 120.0;
+const ZOMBIE_JUMP_TIME: f32 = 1.0;
+// 投掷物碰撞检测时在水平方向的搜索范围，需大于僵尸碰撞箱的宽度
+const ZOMBIE_SEARCH_RANGE: f32 = 200.0;
+// 保龄球可以命中的碰撞箱底部高度
+const BOWLING_HIT_HEIGHT: f32 = 60.0;
+// 追踪投掷物瞄准僵尸的高度
+const FOLLOW_TARGET_HEIGHT: f32 = 60.0;
+// 抛物线投掷物瞄准僵尸的高度
+const THROW_TARGET_HEIGHT: f32 = 50.0;
+// 抛物线投掷物的飞行时间，与水平距离有关
+const THROW_SPEED_X: f32 = 600.0;
+const THROW_MIN_TIME: f32 = 0.6;
+const THROW_MAX_TIME: f32 = 1.2;
+
+// 弹坑缩小的阶段数
+const CRATER_STAGES: u32 = 3;
+
 // 计算斜抛运动的初始速度
 fn calculate_throw_initial_velocity(start: Vec2, end: Vec2, gravity: f32, time: f32) -> Vec2 {
     // 水平方向：匀速运动
@@ -3054,12 +4320,15 @@ pub(crate) fn check_remove_movement(
 pub(crate) fn plant_product(
     mut commands: Commands,
     time: Res<Time>,
-    mut plants: Query<(Entity, &mut PlantProduceTag, &PlantTag, &GlobalTransform)>,
+    mut plants: Query<
+        (Entity, &mut PlantProduceTag, &PlantTag, &GlobalTransform),
+        Without<PlantSleepTag>,
+    >,
     asset_server: Res<AssetServer>,
     mut skeletions: ResMut<Assets<SkeletonData>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let delta = time.delta().as_secs_f32();
-    let mut rng = thread_rng();
     for (entity, mut produce, PlantTag { metadata, .. }, transform) in &mut plants {
         // 生产配置
         let Some(config) = &metadata.produce else {
@@ -3151,12 +4420,15 @@ pub(crate) fn plant_product(
 pub(crate) fn update_plant_instant_timer(
     mut commands: Commands,
     time: Res<Time>,
-    mut plants: Query<(
-        &GlobalTransform,
-        &mut PlantInstantTag,
-        &mut PlantHp,
-        &PlantTag,
-    )>,
+    mut plants: Query<
+        (
+            &GlobalTransform,
+            &mut PlantInstantTag,
+            &mut PlantHp,
+            &PlantTag,
+        ),
+        Without<PlantSleepTag>,
+    >,
     asset_server: Res<AssetServer>,
 ) {
     for (transform, mut instant, mut hp, plant) in &mut plants {
@@ -3191,11 +4463,19 @@ pub(crate) fn update_plant_instant_timer(
                 SceneTag,
             ));
 
-            #[allow(clippy::single_match)]
             match plant_instant.effects[i].effect_type {
                 InstantEffectType::Explode { radius, damage } => {
                     effect_entity.insert(ExplodeEffectTag { radius, damage });
                 }
+                InstantEffectType::Freeze { radius, duration } => {
+                    effect_entity.insert(FreezeEffectTag { radius, duration });
+                }
+                InstantEffectType::Crater { duration } => {
+                    effect_entity.insert(CraterEffectTag {
+                        solt: plant.solt,
+                        duration,
+                    });
+                }
             }
 
             // 粒子效果
@@ -3251,6 +4531,105 @@ pub(crate) fn apply_effect_explode(
     }
 }
 
+// 冰冻逻辑
+pub(crate) fn apply_effect_freeze(
+    mut commands: Commands,
+    placeholder: Query<(Entity, &GlobalTransform, &FreezeEffectTag)>,
+    mut zombies: Query<(&GlobalTransform, &mut ZombieStatus), With<ZombieTag>>,
+) {
+    for (entity, transform, tag) in &placeholder {
+        commands.entity(entity).despawn_recursive();
+
+        let translation = transform.translation();
+        let position = Vec2 {
+            x: translation.x,
+            y: translation.y,
+        };
+        zombies.par_iter_mut().for_each(|(transform, mut status)| {
+            let zombie_translation = transform.translation();
+            let zombie_position = Vec2 {
+                x: zombie_translation.x,
+                y: zombie_translation.y,
+            };
+
+            let distance = position.distance(zombie_position);
+            if distance < tag.radius {
+                status.apply(StatusEffectType::Freeze, tag.duration, 0.0);
+            }
+        });
+    }
+}
+
+// 在格子中留下弹坑，已有弹坑时重新计时
+pub(crate) fn apply_effect_crater(
+    mut commands: Commands,
+    placeholder: Query<(Entity, &CraterEffectTag)>,
+    mut solts: Query<(&mut PlantSolt, &GlobalTransform)>,
+    mut craters: Query<&mut CraterTag>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, tag) in &placeholder {
+        commands.entity(entity).despawn_recursive();
+
+        let Ok((mut solt, transform)) = solts.get_mut(tag.solt) else {
+            continue;
+        };
+        let timer = Timer::new(Duration::from_secs_f32(tag.duration), TimerMode::Once);
+
+        if let Some(mut crater) = solt.hole.and_then(|hole| craters.get_mut(hole).ok()) {
+            crater.timer = timer;
+            continue;
+        }
+
+        let translation = transform.translation();
+        // TODO: 暂无弹坑的贴图，先用深色椭圆代替
+        let crater = commands
+            .spawn((
+                MaterialMesh2dBundle {
+                    mesh: meshes.add(Ellipse::new(38.0, 20.0)).into(),
+                    material: materials.add(Color::srgb(0.3, 0.2, 0.1)),
+                    transform: Transform::from_xyz(translation.x, translation.y - 15.0, 9.0),
+                    ..Default::default()
+                },
+                CraterTag {
+                    solt: tag.solt,
+                    timer,
+                },
+                SceneTag,
+            ))
+            .id();
+        solt.hole = Some(crater);
+    }
+}
+
+// 弹坑分阶段变小，计时结束后消失
+pub(crate) fn update_crater(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut craters: Query<(Entity, &mut CraterTag, &mut Transform)>,
+    mut solts: Query<&mut PlantSolt>,
+) {
+    for (entity, mut crater, mut transform) in &mut craters {
+        if !crater.timer.tick(time.delta()).finished() {
+            let stage = (crater.timer.fraction() * CRATER_STAGES as f32) as u32;
+            let scale = 1.0 - stage as f32 / CRATER_STAGES as f32 * 0.6;
+            if transform.scale.x != scale {
+                transform.scale = Vec3::new(scale, scale, 1.0);
+            }
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
+        let Ok(mut solt) = solts.get_mut(crater.solt) else {
+            continue;
+        };
+        if solt.hole == Some(entity) {
+            solt.hole = None;
+        }
+    }
+}
+
 pub(crate) fn update_plant_hp_anim(
     mut commands: Commands,
     mut plants: Query<(Entity, &PlantHp, &mut PlantHpAnim, &PlantTag)>,
@@ -3297,13 +4676,12 @@ pub(crate) fn provide_conveyor_belt_plant(
     plant_registry: Res<PlantRegistry>,
     mut solt: Query<(&GlobalTransform, &mut ConveyorBeltSolt)>,
     seeds: Query<(), With<ConveyorBeltSeedTag>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let Some(conveyor_belt) = &current_level.conveyor_belt else {
         return;
     };
 
-    let mut rng = thread_rng();
-
     for (transform, mut belt_solt) in &mut solt {
         // 计时器
         belt_solt.timer.tick(time.delta());
@@ -3324,7 +4702,7 @@ pub(crate) fn provide_conveyor_belt_plant(
         } else {
             conveyor_belt
                 .plant_pools
-                .choose_weighted(&mut rng, |plant| plant.weight)
+                .choose_weighted(&mut *rng, |plant| plant.weight)
                 .unwrap()
                 .plant
         };
@@ -3370,6 +4748,14 @@ pub(crate) fn update_conveyor_belt_seed_positiont(
     }
 }
 
+pub(crate) fn predicate_first_round(zombie_wave_controller: Res<ZombieWaveController>) -> bool {
+    zombie_wave_controller.round == 0
+}
+
+pub(crate) fn predicate_versus(current_level: Res<CurrentLevel>) -> bool {
+    current_level.versus.is_some()
+}
+
 pub(crate) fn predicate_bowling(current_level: Res<CurrentLevel>) -> bool {
     matches!(current_level.special_rule, Some(SpecialRule::Bowling))
 }
@@ -3424,7 +4810,7 @@ pub(crate) fn bowling_plant_insert_movetag(
 
 pub(crate) fn bowling_plant_hit(
     mut commands: Commands,
-    mut zombies: Query<&mut ZombieHp, With<ZombieAttackableTag>>,
+    mut zombies: Query<(&mut ZombieHp, &Hitbox), With<ZombieAttackableTag>>,
     mut bowlings: Query<(
         Entity,
         &mut BowlingHitZombieMemory,
@@ -3433,9 +4819,10 @@ pub(crate) fn bowling_plant_hit(
         &mut MoveVelocity,
         &PlantMetaData,
     )>,
-    zombie_kdtree: Res<KDTree2<ZombieAttackableTag>>,
+    zombie_index: Res<LaneIndex<ZombieAttackableTag>>,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (entity, mut memory, mut cooldown, transform, mut velocity, metadata) in &mut bowlings {
         // cd
@@ -3451,34 +4838,32 @@ pub(crate) fn bowling_plant_hit(
             y: translation.y,
         };
 
-        // 最近的僵尸
-        let Some((zombie_position, Some(zombie_entity))) = zombie_kdtree
-            .k_nearest_neighbour(position, memory.0.len() + 1)
-            .into_iter()
-            .filter(|(_, entity)| {
-                entity
-                    .clone()
-                    .map(|entity| !memory.0.contains(&entity))
-                    .unwrap_or(false)
+        // 与保龄球碰撞的最近的僵尸
+        let Some((_, zombie_entity)) = zombie_index
+            .range(
+                None,
+                position.x - ZOMBIE_SEARCH_RANGE,
+                position.x + ZOMBIE_SEARCH_RANGE,
+            )
+            .filter(|(_, entity)| !memory.0.contains(entity))
+            .filter(|(zombie_position, entity)| {
+                zombies.get(*entity).is_ok_and(|(_, hitbox)| {
+                    // 保龄球贴地滚动，只检查碰撞箱底部的一段，避免命中相邻一路的僵尸
+                    let mut collision = hitbox.at(*zombie_position);
+                    collision.max.y = collision.max.y.min(collision.min.y + BOWLING_HIT_HEIGHT);
+                    collision.contains(position)
+                })
+            })
+            .min_by(|(a, _), (b, _)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
             })
-            .next()
         else {
             continue;
         };
 
-        // 僵尸碰撞箱
-        let zombie_collision = Rect {
-            min: zombie_position - Vec2 { x: 31.0, y: -14.0 },
-            max: zombie_position + Vec2 { x: 15.0, y: 45.0 },
-        };
-
-        // 碰撞检测
-        if !zombie_collision.contains(position) {
-            continue;
-        }
-
         // 僵尸信息
-        let Ok(mut zombie_hp) = zombies.get_mut(zombie_entity) else {
+        let Ok((mut zombie_hp, _)) = zombies.get_mut(zombie_entity) else {
             continue;
         };
 
@@ -3529,7 +4914,7 @@ pub(crate) fn bowling_plant_hit(
 
         // 改变移动方向
         if velocity.0.y.abs() <= 0.01 {
-            velocity.0.y = *[200.0, -200.0].choose(&mut thread_rng()).unwrap();
+            velocity.0.y = *[200.0, -200.0].choose(&mut *rng).unwrap();
         } else {
             velocity.0.y = -velocity.0.y;
         }
@@ -3538,12 +4923,10 @@ pub(crate) fn bowling_plant_hit(
         cooldown.0 = 0.2;
 
         // se
-        spawn_se(
+        spawn_random_se(
             &mut commands,
             &asset_server,
-            *["sounds/bowlingimpact.ogg", "sounds/bowlingimpact2.ogg"]
-                .choose(&mut thread_rng())
-                .unwrap(),
+            &["sounds/bowlingimpact.ogg", "sounds/bowlingimpact2.ogg"],
         );
     }
 }
@@ -3566,3 +4949,326 @@ pub(crate) fn bowling_change_velocity(
         }
     }
 }
+
+// 按下 Esc 或窗口失去焦点时暂停，暂停中再次按下 Esc 继续游戏
+pub(crate) fn input_toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut window_focused: EventReader<WindowFocused>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut actions: EventWriter<PlayerAction>,
+) {
+    let lost_focus = window_focused.read().any(|event| !event.focused);
+    let escape = keyboard_input.just_pressed(KeyCode::Escape);
+
+    let next = match state.get() {
+        GameState::Main if escape || lost_focus => GameState::Paused,
+        GameState::Paused if escape => GameState::Main,
+        _ => return,
+    };
+
+    next_state.set(next);
+    actions.send(PlayerAction::TogglePause);
+}
+
+pub(crate) fn click_resume_button(
+    button: Query<&ButtonInteraction, With<ResumeButtonTag>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut actions: EventWriter<PlayerAction>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    next_state.set(GameState::Main);
+    actions.send(PlayerAction::TogglePause);
+}
+
+pub(crate) fn click_restart_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<RestartButtonTag>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+    // 离开暂停状态以恢复时间
+    next_state.set(GameState::Init);
+    next_screen.set(GameScene::Game);
+}
+
+pub(crate) fn click_main_menu_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<MainMenuButtonTag>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+    // 离开暂停状态以恢复时间
+    next_state.set(GameState::Init);
+    next_screen.set(GameScene::Title);
+}
+
+// 点击速度按钮切换游戏速度
+pub(crate) fn click_speed_button(
+    button: Query<&ButtonInteraction, With<SpeedButtonTag>>,
+    mut texts: Query<&mut Text, With<SpeedTextTag>>,
+    mut speed: ResMut<GameSpeed>,
+    mut actions: EventWriter<PlayerAction>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    speed.0 = speed.next();
+    actions.send(PlayerAction::SetSpeed { speed: speed.0 });
+
+    for mut text in &mut texts {
+        text.sections[0].value = speed.label();
+    }
+}
+
+// 将游戏速度应用到虚拟时间与音频
+// Spine 动画、GameTimer 与各类计时器均使用虚拟时间，无需单独处理
+pub(crate) fn update_game_speed(
+    speed: Res<GameSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+    music: Query<&FtxmAudioSink>,
+    audios: Query<&AudioSink>,
+) {
+    if time.relative_speed() != speed.0 {
+        time.set_relative_speed(speed.0);
+    }
+
+    // 新播放的音乐与音效也需要调整速度
+    for FtxmAudioSink { sink } in &music {
+        if sink.speed() != speed.0 {
+            sink.set_speed(speed.0);
+        }
+    }
+    for audio in &audios {
+        if audio.speed() != speed.0 {
+            audio.set_speed(speed.0);
+        }
+    }
+}
+
+// 输入：捡起或放回铲子
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn input_pick_shovel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bank: Query<&GlobalTransform, With<ShovelBankTag>>,
+    pick_shovel: Query<Entity, With<PickShovel>>,
+    pick_seed: Query<Entity, With<PickSeed>>,
+    cursor_position: Res<CursorPosition>,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    mut actions: EventWriter<PlayerAction>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // 点击的铲子栏
+    let Some(translation) = bank
+        .iter()
+        .map(GlobalTransform::translation)
+        .find(|translation| {
+            Rect {
+                min: Vec2 {
+                    x: translation.x,
+                    y: translation.y - 72.0,
+                },
+                max: Vec2 {
+                    x: translation.x + 70.0,
+                    y: translation.y,
+                },
+            }
+            .contains(cursor_position.world_position)
+        })
+    else {
+        return;
+    };
+
+    // 如果点到了铲子栏，清除点击事件
+    mouse_button_input.clear_just_pressed(MouseButton::Left);
+    actions.send(PlayerAction::PickShovel {
+        x: translation.x + 35.0,
+        y: translation.y - 36.0,
+    });
+
+    // 已经捡起铲子，则放回
+    if !pick_shovel.is_empty() {
+        for entity in &pick_shovel {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    // 放下捡起的种子
+    for entity in &pick_seed {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // 生成跟随鼠标的铲子
+    commands
+        .spawn((
+            Transform::default(),
+            GlobalTransform::default(),
+            InheritedVisibility::default(),
+            FollowCursorTag,
+            SceneTag,
+            PickShovel,
+            GameUiTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    anchor: Anchor::BottomLeft,
+                    ..Default::default()
+                },
+                texture: asset_server.load("images/Shovel.png"),
+                transform: Transform::from_xyz(-20.0, -20.0, 20.0),
+                ..Default::default()
+            });
+        });
+
+    spawn_se(&mut commands, &asset_server, "sounds/seedlift.ogg");
+}
+
+// 输入：使用铲子移除植物
+pub(crate) fn shovel_plant(
+    mut commands: Commands,
+    pick_shovel: Query<Entity, With<PickShovel>>,
+    mut solts: Query<(&mut PlantSolt, &GlobalTransform)>,
+    cursor_position: Res<CursorPosition>,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    asset_server: Res<AssetServer>,
+    mut actions: EventWriter<PlayerAction>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) || pick_shovel.is_empty() {
+        return;
+    }
+
+    // 点击的格子
+    let Some((mut solt, solt_position, _)) = solts
+        .iter_mut()
+        .filter_map(|(solt, transform)| {
+            let solt_translation = transform.translation();
+            let solt_position = Vec2 {
+                x: solt_translation.x,
+                y: solt_translation.y,
+            };
+
+            let distance_square = solt_position.distance_squared(cursor_position.world_position);
+            if distance_square > 55.0 * 55.0 {
+                return None;
+            }
+
+            Some((solt, solt_position, distance_square))
+        })
+        .min_by(|(_, _, ds1), (_, _, ds2)| ds1.total_cmp(ds2))
+    else {
+        return;
+    };
+
+    // 如果点到了格子，清除点击事件
+    mouse_button_input.clear_just_pressed(MouseButton::Left);
+    actions.send(PlayerAction::Shovel {
+        x: solt_position.x,
+        y: solt_position.y,
+    });
+
+    // 无论是否移除了植物，都放回铲子
+    for entity in &pick_shovel {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(plant) = solt.shovel_target() else {
+        return;
+    };
+
+    // 移除植物，并清除对应植物槽标记
+    commands.entity(plant).despawn_recursive();
+    solt.plants_mut()
+        .into_iter()
+        .filter(|entity| **entity == Some(plant))
+        .for_each(|entity| *entity = None);
+
+    spawn_se(&mut commands, &asset_server, "sounds/plant2.ogg");
+}
+
+// 高亮铲子将要移除的植物
+pub(crate) fn update_shovel_hover(
+    pick_shovel: Query<(), With<PickShovel>>,
+    solts: Query<(&PlantSolt, &GlobalTransform)>,
+    mut plants: Query<&mut Spine, With<PlantTag>>,
+    cursor_position: Res<CursorPosition>,
+    mut highlight: Local<Option<Entity>>,
+) {
+    let target = if pick_shovel.is_empty() {
+        None
+    } else {
+        solts
+            .iter()
+            .filter_map(|(solt, transform)| {
+                let translation = transform.translation();
+                let distance_square = Vec2 {
+                    x: translation.x,
+                    y: translation.y,
+                }
+                .distance_squared(cursor_position.world_position);
+                (distance_square <= 55.0 * 55.0).then_some((solt, distance_square))
+            })
+            .min_by(|(_, ds1), (_, ds2)| ds1.total_cmp(ds2))
+            .and_then(|(solt, _)| solt.shovel_target())
+    };
+
+    if *highlight == target {
+        return;
+    }
+
+    // 恢复原先高亮的植物
+    if let Some(mut spine) = highlight.and_then(|entity| plants.get_mut(entity).ok()) {
+        *spine.skeleton.color_mut() = bevy_spine::rusty_spine::Color::new_rgba(1.0, 1.0, 1.0, 1.0);
+    }
+    if let Some(mut spine) = target.and_then(|entity| plants.get_mut(entity).ok()) {
+        *spine.skeleton.color_mut() = bevy_spine::rusty_spine::Color::new_rgba(1.5, 1.5, 1.5, 1.0);
+    }
+    *highlight = target;
+}
+
+// 捡起铲子后，隐藏铲子栏中的铲子
+pub(crate) fn update_shovel_bank(
+    pick_shovel: Query<(), With<PickShovel>>,
+    mut shovel: Query<&mut Visibility, With<ShovelTag>>,
+) {
+    let visibility = if pick_shovel.is_empty() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut shovel_visibility in &mut shovel {
+        if *shovel_visibility != visibility {
+            *shovel_visibility = visibility;
+        }
+    }
+}
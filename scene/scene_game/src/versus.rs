@@ -0,0 +1,971 @@
+use std::{ops::Range, time::Duration};
+
+use bevy::{
+    prelude::*,
+    sprite::{Anchor, MaterialMesh2dBundle},
+    text::Text2dBounds,
+};
+use bevy_spine::{Spine, SpineBundle};
+use fw_cursor::CursorPosition;
+use mod_level::CurrentLevel;
+use mod_net::{NetRole, NetSession, VersusCommand};
+use mod_plant::{
+    components::{PlantCooldown, PlantMetaData, PlantSeed, PlantUsable},
+    metadata::PlantInfo,
+};
+use mod_zombie::metadata::ZombieRegistry;
+
+use crate::{
+    resource::{Brains, GameRng, Sunshine, VersusCommands},
+    spatial::LaneIndex,
+    tag::{
+        BrainsText, FollowCameraTag, FollowCursorTag, GameUiTag, LanePosition, MoveVelocity,
+        OnetimeSeedTag, PickZombieSeed, PickableSeed, PlantSideTag, PlantSleepTag, PlantSolt,
+        ProjectileTag, SceneTag, SunshineTag, ThrowProjectileTag, VersusCursor, VersusSelectMarker,
+        VersusTarget, ZombieSeedCard, ZombieSeedOverlay, ZombieSideTag,
+    },
+    update::{
+        can_plant_at, collect_sunshine_entity, plant_solt_position, spawn_plant, spawn_se,
+        spawn_zombie,
+    },
+    GameState,
+};
+
+// 草地的行列数，格子位置与 setup_plant_solt 一致，加载关卡时已排除泳池
+const LANE_COUNT: u8 = 5;
+const COLUMN_COUNT: u8 = 9;
+// 目标位于最右侧一列之外
+const TARGET_X: f32 = 400.0;
+const TARGET_RADIUS: f32 = 30.0;
+
+// 键盘操作的按键
+struct VersusKeys {
+    up: KeyCode,
+    down: KeyCode,
+    left: KeyCode,
+    right: KeyCode,
+    // 切换选中的种子卡
+    prev: KeyCode,
+    next: KeyCode,
+    place: KeyCode,
+}
+
+const PLANT_KEYS: VersusKeys = VersusKeys {
+    up: KeyCode::KeyW,
+    down: KeyCode::KeyS,
+    left: KeyCode::KeyA,
+    right: KeyCode::KeyD,
+    prev: KeyCode::KeyQ,
+    next: KeyCode::KeyE,
+    place: KeyCode::Space,
+};
+
+const ZOMBIE_KEYS: VersusKeys = VersusKeys {
+    up: KeyCode::ArrowUp,
+    down: KeyCode::ArrowDown,
+    left: KeyCode::ArrowLeft,
+    right: KeyCode::ArrowRight,
+    prev: KeyCode::Period,
+    next: KeyCode::Slash,
+    place: KeyCode::Enter,
+};
+
+// 格子中心的位置
+fn cell_position(column: u8, lane: u8) -> Vec2 {
+    Vec2 {
+        x: -320.0 + column as f32 * 80.0,
+        y: lane as f32 * 100.0 - 110.0 * 2.0,
+    }
+}
+
+// 横坐标所在的列
+pub(crate) fn cell_column(x: f32) -> u8 {
+    ((x + 320.0) / 80.0)
+        .round()
+        .clamp(0.0, (COLUMN_COUNT - 1) as f32) as u8
+}
+
+// 种子卡是否可以使用，与鼠标种植相同的检查
+fn seed_ready(
+    sunshine: &Sunshine,
+    plant_info: &PlantInfo,
+    cooldown: &PlantCooldown,
+    usable: &PlantUsable,
+    onetime: bool,
+) -> bool {
+    (sunshine.0 >= plant_info.sunshine
+        && matches!(cooldown, PlantCooldown::Ready)
+        && matches!(usable, PlantUsable::Usable))
+        || onetime
+}
+
+// 僵尸种子卡是否可以使用
+fn card_ready(card: &ZombieSeedCard, brains: &Brains) -> bool {
+    card.cooldown.finished() && brains.count >= card.cost
+}
+
+// 僵尸方的脑子、种子栏、目标，以及双方的键盘光标
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn setup_versus(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+    zombie_registry: Res<ZombieRegistry>,
+    session: Option<Res<NetSession>>,
+    mut brains: ResMut<Brains>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let Some(versus) = &current_level.versus else {
+        return;
+    };
+
+    *brains = Brains {
+        count: versus.brains,
+        timer: Timer::from_seconds(versus.brain_interval, TimerMode::Repeating),
+    };
+
+    // 种子栏，位于右上角
+    let seedbank = asset_server.load("images/seedbank.png");
+    let seed_background = asset_server.load("images/SeedPacket_Larger.png");
+    let width = 80.0 + 12.0 + 5.0 + 55.0 * versus.zombie_seeds.len() as f32;
+    commands
+        .spawn((
+            FollowCameraTag,
+            Transform::default(),
+            GlobalTransform::default(),
+            ViewVisibility::default(),
+            Visibility::default(),
+            InheritedVisibility::default(),
+            SceneTag,
+            GameUiTag,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            anchor: Anchor::TopLeft,
+                            custom_size: Some(Vec2 { x: width, y: 87.0 }),
+                            ..Default::default()
+                        },
+                        texture: seedbank,
+                        transform: Transform::from_xyz(400.0 - width, 300.0, 1.0),
+                        ..Default::default()
+                    },
+                    ImageScaleMode::Sliced(TextureSlicer {
+                        border: BorderRect {
+                            left: 80.0,
+                            right: 12.0,
+                            top: 8.0,
+                            bottom: 8.0,
+                        },
+                        center_scale_mode: SliceScaleMode::Stretch,
+                        sides_scale_mode: SliceScaleMode::Stretch,
+                        max_corner_scale: 1.0,
+                    }),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text2dBundle {
+                            text: Text {
+                                sections: vec![TextSection {
+                                    value: versus.brains.to_string(),
+                                    style: TextStyle {
+                                        color: Color::BLACK,
+                                        ..Default::default()
+                                    },
+                                }],
+                                ..Default::default()
+                            },
+                            text_anchor: Anchor::Center,
+                            text_2d_bounds: Text2dBounds {
+                                size: Vec2 { x: 56.0, y: 22.0 },
+                            },
+                            transform: Transform::from_xyz(39.0, -73.0, 0.01),
+                            ..Default::default()
+                        },
+                        BrainsText,
+                    ));
+
+                    for (i, seed) in versus.zombie_seeds.iter().enumerate() {
+                        let Some(zombie_info) = zombie_registry.get(&seed.zombie) else {
+                            continue;
+                        };
+
+                        // 开局即可使用
+                        let mut cooldown = Timer::from_seconds(seed.cooldown, TimerMode::Once);
+                        cooldown.tick(cooldown.duration());
+
+                        parent
+                            .spawn((
+                                SpatialBundle::from_transform(Transform::from_xyz(
+                                    85.0 + 55.0 * i as f32 + 55.0 * 0.5,
+                                    -8.0 - 35.0,
+                                    0.1,
+                                )),
+                                ZombieSeedCard {
+                                    zombie: seed.zombie,
+                                    cost: seed.cost,
+                                    cooldown,
+                                },
+                            ))
+                            .with_children(|parent| {
+                                // 种子包背景
+                                parent.spawn(SpriteBundle {
+                                    texture: seed_background.clone(),
+                                    transform: Transform::from_scale(Vec3 {
+                                        x: 0.5,
+                                        y: 0.5,
+                                        z: 1.0,
+                                    }),
+                                    ..Default::default()
+                                });
+
+                                // 脑子文字
+                                parent.spawn(Text2dBundle {
+                                    text: Text {
+                                        sections: vec![TextSection {
+                                            value: seed.cost.to_string(),
+                                            style: TextStyle {
+                                                font_size: 14.0,
+                                                color: Color::BLACK,
+                                                ..Default::default()
+                                            },
+                                        }],
+                                        ..Default::default()
+                                    },
+                                    text_anchor: Anchor::CenterRight,
+                                    text_2d_bounds: Text2dBounds {
+                                        size: Vec2 { x: 60.0, y: 18.0 },
+                                    },
+                                    transform: Transform::from_xyz(6.0, -26.0, 0.01),
+                                    ..Default::default()
+                                });
+
+                                // 僵尸
+                                parent.spawn(SpineBundle {
+                                    skeleton: zombie_info.render.spine_skeleton.clone(),
+                                    transform: Transform::from_xyz(0.0, -20.0, 0.01)
+                                        .with_scale(Vec3::ONE * 0.2),
+                                    ..Default::default()
+                                });
+
+                                // 冷却或脑子不足
+                                parent.spawn((
+                                    SpriteBundle {
+                                        sprite: Sprite {
+                                            color: Color::srgba(0.0, 0.0, 0.0, 0.6),
+                                            custom_size: Some(Vec2 { x: 50.0, y: 70.0 }),
+                                            ..Default::default()
+                                        },
+                                        transform: Transform::from_xyz(0.0, 0.0, 0.02),
+                                        visibility: Visibility::Hidden,
+                                        ..Default::default()
+                                    },
+                                    ZombieSeedOverlay,
+                                ));
+                            });
+                    }
+                });
+        });
+
+    // 目标，每一路一个
+    let circles = [
+        (TARGET_RADIUS, Color::srgb(0.8, 0.1, 0.1)),
+        (TARGET_RADIUS * 0.65, Color::WHITE),
+        (TARGET_RADIUS * 0.3, Color::srgb(0.8, 0.1, 0.1)),
+    ]
+    .map(|(radius, color)| (meshes.add(Circle::new(radius)), materials.add(color)));
+    for lane in 0..LANE_COUNT {
+        let Vec2 { y, .. } = cell_position(0, lane);
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(TARGET_X, y, 14.0)),
+                VersusTarget {
+                    hp: versus.target_hp,
+                },
+                LanePosition { lane, x: TARGET_X },
+                SceneTag,
+            ))
+            .with_children(|parent| {
+                for (i, (mesh, material)) in circles.iter().enumerate() {
+                    parent.spawn(MaterialMesh2dBundle {
+                        mesh: mesh.clone().into(),
+                        material: material.clone(),
+                        transform: Transform::from_xyz(0.0, 0.0, i as f32 * 0.01),
+                        ..Default::default()
+                    });
+                }
+            });
+    }
+
+    // 键盘光标，联机时只有本地一方
+    for (column, color, role) in [
+        (0, Color::srgba(0.2, 1.0, 0.2, 0.3), NetRole::Host),
+        (
+            versus.plant_columns,
+            Color::srgba(1.0, 0.2, 0.2, 0.3),
+            NetRole::Join,
+        ),
+    ] {
+        if session.as_ref().is_some_and(|session| session.role != role) {
+            continue;
+        }
+
+        let cursor = (
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2 { x: 80.0, y: 100.0 }),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(cell_position(column, 2).extend(13.0)),
+                ..Default::default()
+            },
+            VersusCursor {
+                lane: 2,
+                column,
+                selected: 0,
+            },
+            SceneTag,
+        );
+        let marker = (
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2 { x: 50.0, y: 70.0 }),
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            VersusSelectMarker,
+            SceneTag,
+        );
+        match role {
+            NetRole::Host => {
+                commands.spawn((cursor, PlantSideTag));
+                commands.spawn((marker, PlantSideTag));
+            }
+            NetRole::Join => {
+                commands.spawn((cursor, ZombieSideTag));
+                commands.spawn((marker, ZombieSideTag));
+            }
+        }
+    }
+}
+
+// 按固定间隔获得脑子
+pub(crate) fn update_brains(
+    time: Res<Time>,
+    current_level: Res<CurrentLevel>,
+    mut brains: ResMut<Brains>,
+) {
+    let Some(versus) = &current_level.versus else {
+        return;
+    };
+
+    brains.timer.tick(time.delta());
+    let times = brains.timer.times_finished_this_tick();
+    brains.count += versus.brain_amount * times;
+}
+
+pub(crate) fn update_brains_text(
+    brains: Res<Brains>,
+    mut text: Query<&mut Text, With<BrainsText>>,
+) {
+    for mut text in &mut text {
+        text.sections[0].value = brains.count.to_string();
+    }
+}
+
+// 僵尸种子卡冷却，冷却中或脑子不足时显示遮罩
+pub(crate) fn update_zombie_seed_card(
+    time: Res<Time>,
+    brains: Res<Brains>,
+    mut cards: Query<(&mut ZombieSeedCard, &Children)>,
+    mut overlay: Query<&mut Visibility, With<ZombieSeedOverlay>>,
+) {
+    for (mut card, children) in &mut cards {
+        card.cooldown.tick(time.delta());
+        let usable = card.cooldown.finished() && brains.count >= card.cost;
+
+        for entity in children {
+            let Ok(mut visibility) = overlay.get_mut(*entity) else {
+                continue;
+            };
+            *visibility = match usable {
+                true => Visibility::Hidden,
+                false => Visibility::Inherited,
+            };
+        }
+    }
+}
+
+// 在格子中放置僵尸，并扣除脑子与重新冷却
+fn place_zombie(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    zombie_registry: &ZombieRegistry,
+    brains: &mut Brains,
+    card: &mut ZombieSeedCard,
+    column: u8,
+    lane: u8,
+) {
+    let Some(zombie_info) = zombie_registry.get(&card.zombie) else {
+        return;
+    };
+
+    // 与 setup_zombie_solt 中的刷怪点高度一致
+    let position = cell_position(column, lane);
+    spawn_zombie(
+        commands,
+        zombie_info,
+        Vec3 {
+            x: position.x,
+            y: position.y - 15.0,
+            z: 0.0,
+        },
+        lane,
+    );
+
+    brains.count -= card.cost;
+    card.cooldown.reset();
+    spawn_se(commands, asset_server, "sounds/plant2.ogg");
+}
+
+// 鼠标：放置捡起的僵尸
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn place_zombie_seed(
+    mut commands: Commands,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    cursor_position: Res<CursorPosition>,
+    current_level: Res<CurrentLevel>,
+    pick: Query<(Entity, &PickZombieSeed)>,
+    cards: Query<&ZombieSeedCard>,
+    brains: Res<Brains>,
+    mut versus_commands: EventWriter<VersusCommand>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(versus) = &current_level.versus else {
+        return;
+    };
+    let Some((pick_entity, PickZombieSeed { seed })) = pick.iter().next() else {
+        return;
+    };
+
+    // 点击的格子，只能在右侧几列
+    let Some((column, lane)) = (versus.plant_columns..COLUMN_COUNT)
+        .flat_map(|column| (0..LANE_COUNT).map(move |lane| (column, lane)))
+        .map(|(column, lane)| {
+            let distance_square =
+                cell_position(column, lane).distance_squared(cursor_position.world_position);
+            (column, lane, distance_square)
+        })
+        .filter(|(_, _, distance_square)| *distance_square <= 55.0 * 55.0)
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .map(|(column, lane, _)| (column, lane))
+    else {
+        return;
+    };
+
+    // 如果点到了格子，清除点击事件
+    mouse_button_input.clear_just_pressed(MouseButton::Left);
+
+    let Ok(card) = cards.get(*seed) else {
+        return;
+    };
+    if !card_ready(card, &brains) {
+        return;
+    }
+
+    commands.entity(pick_entity).despawn_recursive();
+    versus_commands.send(VersusCommand::PlaceZombie {
+        zombie: card.zombie,
+        column,
+        lane,
+    });
+}
+
+// 鼠标：捡起僵尸种子卡
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn input_pick_zombie_seed(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    cursor_position: Res<CursorPosition>,
+    cards: Query<(Entity, &ZombieSeedCard, &GlobalTransform)>,
+    pick: Query<(Entity, &PickZombieSeed)>,
+    zombie_registry: Res<ZombieRegistry>,
+    brains: Res<Brains>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // 点击的种子卡
+    let Some((entity, card, _)) = cards.iter().find(|(_, _, transform)| {
+        let translation = transform.translation();
+        Rect::from_center_size(
+            Vec2 {
+                x: translation.x,
+                y: translation.y,
+            },
+            Vec2 { x: 50.0, y: 70.0 },
+        )
+        .contains(cursor_position.world_position)
+    }) else {
+        return;
+    };
+    mouse_button_input.clear_just_pressed(MouseButton::Left);
+
+    // 放下已捡起的种子卡，再次点击同一张卡时不再捡起
+    let mut same_seed = false;
+    for (pick_entity, PickZombieSeed { seed }) in &pick {
+        same_seed |= *seed == entity;
+        commands.entity(pick_entity).despawn_recursive();
+    }
+    if same_seed {
+        return;
+    }
+
+    let Some(zombie_info) = zombie_registry.get(&card.zombie) else {
+        return;
+    };
+    if !card_ready(card, &brains) {
+        spawn_se(&mut commands, &asset_server, "sounds/buzzer.ogg");
+        return;
+    }
+
+    // 生成跟随鼠标的僵尸
+    commands
+        .spawn((
+            Transform::default(),
+            GlobalTransform::default(),
+            InheritedVisibility::default(),
+            FollowCursorTag,
+            SceneTag,
+            PickZombieSeed { seed: entity },
+            GameUiTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(SpineBundle {
+                skeleton: zombie_info.render.spine_skeleton.clone(),
+                transform: Transform::from_xyz(0.0, -30.0, 10.0).with_scale(Vec3::ONE * 0.7),
+                ..Default::default()
+            });
+        });
+    spawn_se(&mut commands, &asset_server, "sounds/seedlift.ogg");
+}
+
+// 移动光标与切换选中的种子卡
+fn move_cursor(
+    cursor: &mut VersusCursor,
+    keys: &VersusKeys,
+    keyboard_input: &ButtonInput<KeyCode>,
+    columns: Range<u8>,
+    seed_count: usize,
+) {
+    if keyboard_input.just_pressed(keys.up) && cursor.lane + 1 < LANE_COUNT {
+        cursor.lane += 1;
+    }
+    if keyboard_input.just_pressed(keys.down) && cursor.lane > 0 {
+        cursor.lane -= 1;
+    }
+    if keyboard_input.just_pressed(keys.left) && cursor.column > columns.start {
+        cursor.column -= 1;
+    }
+    if keyboard_input.just_pressed(keys.right) && cursor.column + 1 < columns.end {
+        cursor.column += 1;
+    }
+    cursor.column = cursor.column.clamp(columns.start, columns.end.max(1) - 1);
+
+    if seed_count == 0 {
+        return;
+    }
+    if keyboard_input.just_pressed(keys.prev) {
+        cursor.selected = (cursor.selected + seed_count - 1) % seed_count;
+    }
+    if keyboard_input.just_pressed(keys.next) {
+        cursor.selected = (cursor.selected + 1) % seed_count;
+    }
+    cursor.selected = cursor.selected.min(seed_count - 1);
+}
+
+// 键盘：植物方
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn input_plant_keyboard(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    current_level: Res<CurrentLevel>,
+    mut cursor: Query<(&mut VersusCursor, &mut Transform), With<PlantSideTag>>,
+    seeds: Query<
+        (
+            &PlantMetaData,
+            &PlantCooldown,
+            &PlantUsable,
+            Has<OnetimeSeedTag>,
+            &GlobalTransform,
+        ),
+        (With<PlantSeed>, With<PickableSeed>),
+    >,
+    asset_server: Res<AssetServer>,
+    sunshine: Res<Sunshine>,
+    mut versus_commands: EventWriter<VersusCommand>,
+) {
+    let Some(versus) = &current_level.versus else {
+        return;
+    };
+    let Ok((mut cursor, mut cursor_transform)) = cursor.get_single_mut() else {
+        return;
+    };
+
+    let mut seeds = seeds.iter().collect::<Vec<_>>();
+    seeds.sort_by(|a, b| a.4.translation().x.total_cmp(&b.4.translation().x));
+
+    move_cursor(
+        &mut cursor,
+        &PLANT_KEYS,
+        &keyboard_input,
+        0..versus.plant_columns,
+        seeds.len(),
+    );
+    let position = cell_position(cursor.column, cursor.lane);
+    cursor_transform.translation = position.extend(cursor_transform.translation.z);
+
+    if !keyboard_input.just_pressed(PLANT_KEYS.place) {
+        return;
+    }
+    let Some((PlantMetaData(plant_info), cooldown, usable, onetime, _)) =
+        seeds.get(cursor.selected)
+    else {
+        return;
+    };
+    if !seed_ready(&sunshine, plant_info, cooldown, usable, *onetime) {
+        spawn_se(&mut commands, &asset_server, "sounds/buzzer.ogg");
+        return;
+    }
+
+    versus_commands.send(VersusCommand::PlantSeed {
+        plant: plant_info.id,
+        column: cursor.column,
+        lane: cursor.lane,
+    });
+}
+
+// 键盘：僵尸方
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn input_zombie_keyboard(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    current_level: Res<CurrentLevel>,
+    mut cursor: Query<(&mut VersusCursor, &mut Transform), With<ZombieSideTag>>,
+    cards: Query<(&ZombieSeedCard, &GlobalTransform)>,
+    brains: Res<Brains>,
+    asset_server: Res<AssetServer>,
+    mut versus_commands: EventWriter<VersusCommand>,
+) {
+    let Some(versus) = &current_level.versus else {
+        return;
+    };
+    let Ok((mut cursor, mut cursor_transform)) = cursor.get_single_mut() else {
+        return;
+    };
+
+    let mut cards = cards.iter().collect::<Vec<_>>();
+    cards.sort_by(|a, b| a.1.translation().x.total_cmp(&b.1.translation().x));
+
+    move_cursor(
+        &mut cursor,
+        &ZOMBIE_KEYS,
+        &keyboard_input,
+        versus.plant_columns..COLUMN_COUNT,
+        cards.len(),
+    );
+    let position = cell_position(cursor.column, cursor.lane);
+    cursor_transform.translation = position.extend(cursor_transform.translation.z);
+
+    if !keyboard_input.just_pressed(ZOMBIE_KEYS.place) {
+        return;
+    }
+    let Some((card, _)) = cards.get(cursor.selected) else {
+        return;
+    };
+    if !card_ready(card, &brains) {
+        spawn_se(&mut commands, &asset_server, "sounds/buzzer.ogg");
+        return;
+    }
+
+    versus_commands.send(VersusCommand::PlaceZombie {
+        zombie: card.zombie,
+        column: cursor.column,
+        lane: cursor.lane,
+    });
+}
+
+// 执行收集阳光的操作
+pub(crate) fn apply_sunshine_commands(
+    mut commands: Commands,
+    versus_commands: Res<VersusCommands>,
+    mut sunshine: ResMut<Sunshine>,
+    mut sunshines: Query<(
+        Entity,
+        Option<&mut Spine>,
+        &SunshineTag,
+        &Transform,
+        &GlobalTransform,
+    )>,
+    asset_server: Res<AssetServer>,
+) {
+    for command in &versus_commands.0 {
+        let VersusCommand::CollectSunshine { x, y } = command else {
+            continue;
+        };
+
+        // 距离点击位置最近的阳光
+        let target = Vec2 { x: *x, y: *y };
+        let Some((sunshine_entity, spine, sunshine_tag, transform, _)) = sunshines
+            .iter_mut()
+            .filter(|(_, _, _, _, global_transform)| {
+                global_transform
+                    .translation()
+                    .truncate()
+                    .distance_squared(target)
+                    <= 55.0 * 55.0
+            })
+            .min_by(|a, b| {
+                let a = a.4.translation().truncate().distance_squared(target);
+                let b = b.4.translation().truncate().distance_squared(target);
+                a.total_cmp(&b)
+            })
+        else {
+            continue;
+        };
+
+        collect_sunshine_entity(
+            &mut commands,
+            &asset_server,
+            &mut sunshine,
+            sunshine_entity,
+            spine,
+            sunshine_tag,
+            transform,
+        );
+    }
+}
+
+// 执行种植的操作，检查与鼠标种植相同
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn apply_plant_commands(
+    mut commands: Commands,
+    versus_commands: Res<VersusCommands>,
+    mut seeds: Query<
+        (
+            Entity,
+            &PlantMetaData,
+            &mut PlantCooldown,
+            &PlantUsable,
+            Has<OnetimeSeedTag>,
+        ),
+        (With<PlantSeed>, With<PickableSeed>),
+    >,
+    mut solts: Query<(Entity, &mut PlantSolt, &GlobalTransform, &LanePosition)>,
+    plants: Query<(&PlantMetaData, Has<PlantSleepTag>)>,
+    asset_server: Res<AssetServer>,
+    mut sunshine: ResMut<Sunshine>,
+    mut rng: ResMut<GameRng>,
+) {
+    for command in &versus_commands.0 {
+        let VersusCommand::PlantSeed {
+            plant,
+            column,
+            lane,
+        } = command
+        else {
+            continue;
+        };
+
+        // 同类植物中可以使用的种子卡
+        let Some((seed_entity, PlantMetaData(plant_info), mut cooldown, _, onetime)) =
+            seeds.iter_mut().find(
+                |(_, PlantMetaData(plant_info), cooldown, usable, onetime)| {
+                    plant_info.id == *plant
+                        && seed_ready(&sunshine, plant_info, cooldown, usable, *onetime)
+                },
+            )
+        else {
+            continue;
+        };
+
+        // 对应格子中可以种植该植物
+        let position = cell_position(*column, *lane);
+        let Some((solt_entity, mut solt, solt_transform, lane_position)) =
+            solts.iter_mut().find(|(_, _, transform, _)| {
+                transform
+                    .translation()
+                    .truncate()
+                    .distance_squared(position)
+                    < 1.0
+            })
+        else {
+            continue;
+        };
+        let Some(solt_position) = plant_solt_position(&mut solt, plant_info, &plants)
+            .filter(|solt_position| can_plant_at(solt_position, plant_info, &plants))
+        else {
+            continue;
+        };
+
+        // 升级植物替换原有的植物
+        if let Some(plant) = solt_position.take() {
+            commands.entity(plant).despawn_recursive();
+        }
+
+        *solt_position = Some(spawn_plant(
+            &mut commands,
+            &asset_server,
+            &mut rng,
+            plant_info,
+            solt_entity,
+            solt_transform.translation(),
+            lane_position.lane,
+        ));
+
+        if onetime {
+            commands.entity(seed_entity).despawn_recursive();
+        } else {
+            sunshine.0 -= plant_info.sunshine;
+            *cooldown = PlantCooldown::Cooldown(Duration::from_secs_f32(plant_info.cooldown));
+        }
+    }
+}
+
+// 执行放置僵尸的操作
+pub(crate) fn apply_zombie_commands(
+    mut commands: Commands,
+    versus_commands: Res<VersusCommands>,
+    mut cards: Query<&mut ZombieSeedCard>,
+    zombie_registry: Res<ZombieRegistry>,
+    mut brains: ResMut<Brains>,
+    asset_server: Res<AssetServer>,
+) {
+    for command in &versus_commands.0 {
+        let VersusCommand::PlaceZombie {
+            zombie,
+            column,
+            lane,
+        } = command
+        else {
+            continue;
+        };
+
+        // 同类僵尸中可以使用的种子卡
+        let Some(mut card) = cards
+            .iter_mut()
+            .find(|card| card.zombie == *zombie && card_ready(card, &brains))
+        else {
+            continue;
+        };
+
+        place_zombie(
+            &mut commands,
+            &asset_server,
+            &zombie_registry,
+            &mut brains,
+            &mut card,
+            *column,
+            *lane,
+        );
+    }
+}
+
+// 标记键盘光标选中的种子卡
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_select_marker(
+    cursors: Query<(&VersusCursor, Has<PlantSideTag>)>,
+    mut markers: Query<
+        (&mut Transform, &mut Visibility, Has<PlantSideTag>),
+        With<VersusSelectMarker>,
+    >,
+    seeds: Query<&GlobalTransform, (With<PlantSeed>, With<PickableSeed>)>,
+    cards: Query<&GlobalTransform, With<ZombieSeedCard>>,
+) {
+    for (cursor, plant_side) in &cursors {
+        let mut positions = match plant_side {
+            true => seeds
+                .iter()
+                .map(GlobalTransform::translation)
+                .collect::<Vec<_>>(),
+            false => cards
+                .iter()
+                .map(GlobalTransform::translation)
+                .collect::<Vec<_>>(),
+        };
+        positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        for (mut transform, mut visibility, marker_plant_side) in &mut markers {
+            if marker_plant_side != plant_side {
+                continue;
+            }
+            match positions.get(cursor.selected) {
+                Some(position) => {
+                    transform.translation = position.truncate().extend(position.z + 0.5);
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
+
+// 投掷物命中目标，任意目标被摧毁则植物方获胜
+#[allow(clippy::type_complexity)]
+pub(crate) fn versus_target_damage(
+    mut commands: Commands,
+    mut targets: Query<&mut VersusTarget>,
+    projectiles: Query<(
+        Entity,
+        &ProjectileTag,
+        &GlobalTransform,
+        Option<&LanePosition>,
+        Option<&MoveVelocity>,
+        Has<ThrowProjectileTag>,
+    )>,
+    target_index: Res<LaneIndex<VersusTarget>>,
+    asset_server: Res<AssetServer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (projectile_entity, projectile, transform, lane, velocity, throw) in &projectiles {
+        // 只结算沿路飞行的投掷物，抛物线投掷物在下落时结算
+        let Some(lane) = lane else {
+            continue;
+        };
+        if throw && velocity.is_some_and(|velocity| velocity.0.y > 0.0) {
+            continue;
+        }
+
+        let x = transform.translation().x;
+        let Some((_, target_entity)) = target_index
+            .range(Some(lane.lane), x - TARGET_RADIUS, x + TARGET_RADIUS)
+            .next()
+        else {
+            continue;
+        };
+        let Ok(mut target) = targets.get_mut(target_entity) else {
+            continue;
+        };
+
+        target.hp -= projectile.info.damage;
+        commands.entity(projectile_entity).despawn_recursive();
+        spawn_se(&mut commands, &asset_server, "sounds/splat.ogg");
+
+        if target.hp <= 0.0 {
+            debug!("gameover: target destroyed, plant win");
+            commands.entity(target_entity).despawn_recursive();
+            next_state.set(GameState::Exit);
+        }
+    }
+}
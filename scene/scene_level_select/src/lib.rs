@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use scene_base::GameScene;
+
+mod setup;
+mod tag;
+mod update;
+
+pub struct SceneLevelSelectPlugin;
+
+impl Plugin for SceneLevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameScene::LevelSelect),
+            (
+                setup::setup_bgm,
+                setup::setup_camera,
+                setup::setup_background,
+                setup::setup_title,
+                setup::setup_levels,
+                setup::setup_back_button,
+            ),
+        )
+        .add_systems(OnExit(GameScene::LevelSelect), setup::clear_scene)
+        .add_systems(
+            Update,
+            (update::click_level_button, update::click_back_button)
+                .run_if(in_state(GameScene::LevelSelect)),
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use bevy::{prelude::*, render::camera::ScalingMode, sprite::Anchor};
+use fw_button::components::{ButtonBackground, ButtonBundle, ButtonEnabled, ButtonHotspot};
+use fw_ftxm::{FtxmSource, MainMusicTable};
+use mod_level::{Level, LevelBackground, LevelRegistry, LevelType};
+use mod_userdata::UserData;
+
+use crate::tag::{BackButtonTag, LevelButtonTag, SceneTag};
+
+// 按场地分区，每个区域一列，生存模式解锁后单独占最后一列
+const AREA_NAMES: [&str; 6] = ["白天", "黑夜", "泳池", "迷雾", "屋顶", "生存"];
+const SURVIVAL_AREA: usize = 5;
+const AREA_LEFT: f32 = -325.0;
+const AREA_SPACING: f32 = 130.0;
+const LEVEL_TOP: f32 = 160.0;
+const LEVEL_SPACING: f32 = 40.0;
+
+// 屋顶黑夜的BOSS战也归入屋顶
+fn level_area(level: &Level) -> usize {
+    if matches!(level.id, LevelType::Survival { .. }) {
+        return SURVIVAL_AREA;
+    }
+    match level.background {
+        LevelBackground::Day { .. } => 0,
+        LevelBackground::Night => 1,
+        LevelBackground::Swim => 2,
+        LevelBackground::SwimFog => 3,
+        LevelBackground::Roof | LevelBackground::RoofNight => 4,
+    }
+}
+
+// 通关过冒险模式后所有关卡都可以重玩，否则只能玩到当前进度
+// 生存模式的关卡在解锁生存模式后都可以选择
+fn level_unlocked(level: LevelType, userdata: &UserData) -> bool {
+    match level {
+        LevelType::Adventure { level } => {
+            userdata.pass_adventure_count > 0 || level <= userdata.adventure_progress
+        }
+        LevelType::Survival { .. } => userdata.unlock_survial,
+        LevelType::PlantZombie => false,
+    }
+}
+
+pub(crate) fn setup_bgm(mut commands: Commands) {
+    commands.spawn((
+        FtxmSource {
+            pot: MainMusicTable::ChooseYourSeeds.into(),
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                far: 1000.,
+                near: -1000.,
+                scaling_mode: ScalingMode::Fixed {
+                    width: 800.,
+                    height: 600.,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_background(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let image = asset_server.load("images/AwardScreen_Back.jpg");
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_title(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    userdata: Res<UserData>,
+) {
+    let font: Handle<Font> = asset_server.load("font/fzcgbk.ttf");
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "选择关卡",
+                TextStyle {
+                    font: font.clone(),
+                    color: Color::srgb(0.8, 0.5, 0.2),
+                    ..Default::default()
+                },
+            ),
+            transform: Transform::from_xyz(0.0, 250., 1.0),
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+
+    for (i, name) in AREA_NAMES.into_iter().enumerate() {
+        if i == SURVIVAL_AREA && !userdata.unlock_survial {
+            continue;
+        }
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    name,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::srgb(0.2, 0.5, 0.8),
+                    },
+                ),
+                transform: Transform::from_xyz(AREA_LEFT + AREA_SPACING * i as f32, 200., 1.0),
+                ..Default::default()
+            },
+            SceneTag,
+        ));
+    }
+}
+
+// 冒险模式的关卡按场地分列，按关卡顺序排列
+// 生存模式解锁后，生存关卡（包括无尽模式）排在最后一列
+pub(crate) fn setup_levels(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_registry: Res<LevelRegistry>,
+    userdata: Res<UserData>,
+) {
+    let mut levels: Vec<_> = level_registry
+        .values()
+        .filter_map(|level| match level.id {
+            LevelType::Adventure { level: index } => Some(((0, index), level)),
+            LevelType::Survival { level: index } if userdata.unlock_survial => {
+                Some(((1, index), level))
+            }
+            _ => None,
+        })
+        .collect();
+    levels.sort_by_key(|(index, _)| *index);
+
+    let font = asset_server.load("font/fzcgbk.ttf");
+    let mut area_counts = [0; AREA_NAMES.len()];
+    for (_, level) in levels {
+        let area = level_area(level);
+        let translation = Vec3 {
+            x: AREA_LEFT + AREA_SPACING * area as f32,
+            y: LEVEL_TOP - LEVEL_SPACING * area_counts[area] as f32,
+            z: 10.0,
+        };
+        area_counts[area] += 1;
+
+        let unlocked = level_unlocked(level.id, &userdata);
+        // 当前进度的关卡高亮显示
+        let current = level.id
+            == LevelType::Adventure {
+                level: userdata.adventure_progress,
+            };
+        let color = match (unlocked, current) {
+            (false, _) => Color::srgb(0.5, 0.5, 0.5),
+            (true, true) => Color::srgb(0.05, 0.6, 0.0),
+            (true, false) => Color::BLACK,
+        };
+
+        commands
+            .spawn((
+                ButtonBundle {
+                    background: ButtonBackground {
+                        normal: asset_server.load("images/SeedChooser_Button2.png"),
+                        hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                        pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                        disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                    },
+                    sprite: SpriteBundle {
+                        sprite: Sprite {
+                            anchor: Anchor::Center,
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(translation),
+                        ..Default::default()
+                    },
+                    hotspot: ButtonHotspot::Rects(vec![Rect {
+                        min: Vec2 { x: -56.0, y: -13.0 },
+                        max: Vec2 { x: 56.0, y: 13.0 },
+                    }]),
+                    enabled: match unlocked {
+                        true => ButtonEnabled::Enabled,
+                        false => ButtonEnabled::Disabled,
+                    },
+                    ..Default::default()
+                },
+                LevelButtonTag { level: level.id },
+                SceneTag,
+            ))
+            .with_children(|parent| {
+                parent.spawn(Text2dBundle {
+                    text: Text::from_section(
+                        match unlocked {
+                            true => level.name.clone(),
+                            false => "未解锁".to_owned(),
+                        },
+                        TextStyle {
+                            font: font.clone(),
+                            color,
+                            font_size: 14.0,
+                        },
+                    ),
+                    text_anchor: Anchor::Center,
+                    transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                    ..Default::default()
+                });
+            });
+    }
+}
+
+pub(crate) fn setup_back_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(330.0, 270., 10.0),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -56.0, y: -13.0 },
+                    max: Vec2 { x: 56.0, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            BackButtonTag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    "主菜单",
+                    TextStyle {
+                        font: font.clone(),
+                        color: Color::BLACK,
+                        font_size: 14.0,
+                    },
+                ),
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn clear_scene(mut commands: Commands, targets: Query<Entity, With<SceneTag>>) {
+    for entity in &targets {
+        commands.entity(entity).despawn_recursive();
+    }
+}
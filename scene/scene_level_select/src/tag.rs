@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use mod_level::LevelType;
+
+#[derive(Debug, Component)]
+pub(crate) struct SceneTag;
+
+// 关卡按钮，未解锁的关卡不可点击
+#[derive(Debug, Component)]
+pub(crate) struct LevelButtonTag {
+    pub level: LevelType,
+}
+
+#[derive(Debug, Component)]
+pub(crate) struct BackButtonTag;
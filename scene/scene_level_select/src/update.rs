@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use fw_button::components::{ButtonEnabled, ButtonInteraction};
+use mod_level::{CurrentLevel, LevelRegistry};
+use scene_base::GameScene;
+
+use crate::tag::{BackButtonTag, LevelButtonTag};
+
+pub(crate) fn click_level_button(
+    mut buttons: Query<(&mut ButtonEnabled, &ButtonInteraction, &LevelButtonTag)>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_registry: Res<LevelRegistry>,
+) {
+    let Some((mut enabled, _, LevelButtonTag { level })) = buttons
+        .iter_mut()
+        .find(|(_, interaction, _)| matches!(interaction, ButtonInteraction::Click))
+    else {
+        return;
+    };
+    let Some(level) = level_registry.get(level) else {
+        return;
+    };
+
+    *enabled = ButtonEnabled::Disabled;
+
+    current_level.0 = level.clone();
+    next_screen.set(GameScene::Game);
+}
+
+pub(crate) fn click_back_button(
+    button: Query<&ButtonInteraction, With<BackButtonTag>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+    next_screen.set(GameScene::Title);
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use mod_net::NetSession;
+use resource::LobbyAddress;
+use scene_base::GameScene;
+
+mod resource;
+mod setup;
+mod tag;
+mod update;
+
+pub struct SceneLobbyPlugin;
+
+impl Plugin for SceneLobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LobbyAddress>()
+            .add_systems(
+                OnEnter(GameScene::Lobby),
+                (
+                    setup::setup_bgm,
+                    setup::setup_camera,
+                    setup::setup_background,
+                    setup::setup_text,
+                    setup::setup_buttons,
+                ),
+            )
+            .add_systems(OnExit(GameScene::Lobby), setup::clear_scene)
+            .add_systems(
+                Update,
+                (
+                    (update::input_address, update::update_address_text).chain(),
+                    update::click_local_button,
+                    update::click_host_button,
+                    update::click_join_button,
+                    update::click_back_button,
+                    (update::update_status_text, update::start_online_game)
+                        .run_if(resource_exists::<NetSession>),
+                )
+                    .run_if(in_state(GameScene::Lobby)),
+            );
+    }
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+// 创建房间时监听的地址，或加入房间时连接的地址
+#[derive(Resource)]
+pub(crate) struct LobbyAddress(pub String);
+
+impl Default for LobbyAddress {
+    fn default() -> Self {
+        Self("127.0.0.1:7878".to_owned())
+    }
+}
@@ -0,0 +1,255 @@
+use bevy::{prelude::*, render::camera::ScalingMode, sprite::Anchor};
+use fw_button::components::{ButtonBackground, ButtonBundle, ButtonHotspot};
+use fw_ftxm::{FtxmSource, MainMusicTable};
+
+use crate::{
+    resource::LobbyAddress,
+    tag::{
+        AddressTextTag, BackButtonTag, HostButtonTag, JoinButtonTag, LocalButtonTag, SceneTag,
+        StatusTextTag,
+    },
+};
+
+pub(crate) fn setup_bgm(mut commands: Commands) {
+    commands.spawn((
+        FtxmSource {
+            pot: MainMusicTable::Loonboon.into(),
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                far: 1000.,
+                near: -1000.,
+                scaling_mode: ScalingMode::Fixed {
+                    width: 800.,
+                    height: 600.,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_background(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let image = asset_server.load("images/AwardScreen_Back.jpg");
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+fn spawn_text(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    text: String,
+    color: Color,
+    y: f32,
+    tag: impl Bundle,
+) {
+    commands.spawn((
+        Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: text,
+                    style: TextStyle {
+                        font: font.clone(),
+                        color,
+                        ..Default::default()
+                    },
+                }],
+                ..Default::default()
+            },
+            text_anchor: Anchor::Center,
+            transform: Transform::from_xyz(0.0, y, 1.0),
+            ..Default::default()
+        },
+        SceneTag,
+        tag,
+    ));
+}
+
+pub(crate) fn setup_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    address: Res<LobbyAddress>,
+) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    spawn_text(
+        &mut commands,
+        &font,
+        "对战模式".to_owned(),
+        Color::srgb(0.8, 0.5, 0.2),
+        250.0,
+        (),
+    );
+    spawn_text(
+        &mut commands,
+        &font,
+        "主机地址（键盘输入）".to_owned(),
+        Color::srgb(0.2, 0.5, 0.8),
+        140.0,
+        (),
+    );
+    spawn_text(
+        &mut commands,
+        &font,
+        address.0.clone(),
+        Color::BLACK,
+        100.0,
+        AddressTextTag,
+    );
+    spawn_text(
+        &mut commands,
+        &font,
+        String::new(),
+        Color::srgb(0.2, 0.5, 0.8),
+        -230.0,
+        StatusTextTag,
+    );
+}
+
+fn spawn_button(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    font: &Handle<Font>,
+    text: &str,
+    y: f32,
+    tag: impl Component,
+) {
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button.png"),
+                    hover: asset_server.load("images/SeedChooser_Button_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button_Disabled.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(0.0, y, 10.0),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -78.0, y: -21.0 },
+                    max: Vec2 { x: 78.0, y: 21.0 },
+                }]),
+                ..Default::default()
+            },
+            tag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: text.to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            color: Color::srgb(0.83, 0.67, 0.07),
+                            ..Default::default()
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn setup_buttons(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    spawn_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "本地对战",
+        20.0,
+        LocalButtonTag,
+    );
+    spawn_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "创建房间",
+        -50.0,
+        HostButtonTag,
+    );
+    spawn_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "加入房间",
+        -120.0,
+        JoinButtonTag,
+    );
+
+    // 返回按钮
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(330.0, 270., 10.0),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -56.0, y: -13.0 },
+                    max: Vec2 { x: 56.0, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            BackButtonTag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: "返回".to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            color: Color::BLACK,
+                            font_size: 14.0,
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn clear_scene(mut commands: Commands, targets: Query<Entity, With<SceneTag>>) {
+    for entity in &targets {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Component)]
+pub(crate) struct SceneTag;
+
+// 主机地址文本
+#[derive(Debug, Component)]
+pub(crate) struct AddressTextTag;
+
+// 连接状态文本
+#[derive(Debug, Component)]
+pub(crate) struct StatusTextTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct LocalButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct HostButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct JoinButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct BackButtonTag;
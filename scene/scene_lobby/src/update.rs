@@ -0,0 +1,191 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use fw_button::components::{ButtonEnabled, ButtonInteraction};
+use mod_level::{CurrentLevel, LevelRegistry, LevelType};
+use mod_net::{NetSession, NetStatus};
+use scene_base::GameScene;
+
+use crate::{
+    resource::LobbyAddress,
+    tag::{
+        AddressTextTag, BackButtonTag, HostButtonTag, JoinButtonTag, LocalButtonTag, StatusTextTag,
+    },
+};
+
+// 地址的最大长度
+const MAX_ADDRESS_LEN: usize = 64;
+
+fn enter_versus(
+    current_level: &mut CurrentLevel,
+    level_registry: &LevelRegistry,
+    next_screen: &mut NextState<GameScene>,
+) {
+    current_level.0 = level_registry.get(&LevelType::PlantZombie).unwrap().clone();
+    next_screen.set(GameScene::Game);
+}
+
+// 已经创建或加入房间时不能修改地址
+pub(crate) fn input_address(
+    mut events: EventReader<KeyboardInput>,
+    mut address: ResMut<LobbyAddress>,
+    session: Option<Res<NetSession>>,
+) {
+    let editable = session.map_or(true, |session| session.error().is_some());
+
+    for event in events.read() {
+        if !editable || event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Backspace => {
+                address.0.pop();
+            }
+            Key::Character(text) => {
+                for c in text.chars() {
+                    if c.is_ascii_graphic() && address.0.len() < MAX_ADDRESS_LEN {
+                        address.0.push(c);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn update_address_text(
+    address: Res<LobbyAddress>,
+    mut texts: Query<&mut Text, With<AddressTextTag>>,
+) {
+    if !address.is_changed() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value.clone_from(&address.0);
+    }
+}
+
+pub(crate) fn click_local_button(
+    mut commands: Commands,
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<LocalButtonTag>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_registry: Res<LevelRegistry>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+    // 本地对战不需要连接
+    commands.remove_resource::<NetSession>();
+    enter_versus(&mut current_level, &level_registry, &mut next_screen);
+}
+
+pub(crate) fn click_host_button(
+    mut commands: Commands,
+    button: Query<&ButtonInteraction, With<HostButtonTag>>,
+    address: Res<LobbyAddress>,
+    mut texts: Query<&mut Text, With<StatusTextTag>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    // 替换旧的会话时会关闭其连接
+    match NetSession::host(&address.0) {
+        Ok(session) => commands.insert_resource(session),
+        Err(err) => {
+            commands.remove_resource::<NetSession>();
+            for mut text in &mut texts {
+                text.sections[0].value = format!("创建房间失败：{err}");
+            }
+        }
+    }
+}
+
+pub(crate) fn click_join_button(
+    mut commands: Commands,
+    button: Query<&ButtonInteraction, With<JoinButtonTag>>,
+    address: Res<LobbyAddress>,
+    mut texts: Query<&mut Text, With<StatusTextTag>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    match NetSession::join(&address.0) {
+        Ok(session) => commands.insert_resource(session),
+        Err(err) => {
+            commands.remove_resource::<NetSession>();
+            for mut text in &mut texts {
+                text.sections[0].value = format!("加入房间失败：{err}");
+            }
+        }
+    }
+}
+
+pub(crate) fn click_back_button(
+    mut commands: Commands,
+    button: Query<&ButtonInteraction, With<BackButtonTag>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    commands.remove_resource::<NetSession>();
+    next_screen.set(GameScene::Title);
+}
+
+pub(crate) fn update_status_text(
+    session: Res<NetSession>,
+    address: Res<LobbyAddress>,
+    mut texts: Query<&mut Text, With<StatusTextTag>>,
+) {
+    let status = match &session.status {
+        NetStatus::Listening => format!("等待对方加入（{}）", address.0),
+        NetStatus::Connecting => "正在连接……".to_owned(),
+        NetStatus::Handshake => "正在等待对方响应……".to_owned(),
+        NetStatus::Ready => "连接成功".to_owned(),
+        NetStatus::Error(err) => err.to_string(),
+    };
+
+    for mut text in &mut texts {
+        if text.sections[0].value != status {
+            text.sections[0].value.clone_from(&status);
+        }
+    }
+}
+
+// 双方握手完成后进入对局
+pub(crate) fn start_online_game(
+    session: Res<NetSession>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_registry: Res<LevelRegistry>,
+) {
+    if session.status != NetStatus::Ready {
+        return;
+    }
+
+    enter_versus(&mut current_level, &level_registry, &mut next_screen);
+}
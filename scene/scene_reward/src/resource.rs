@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// 信件当前显示的页码，从 0 开始
+#[derive(Debug, Resource, Default)]
+pub(crate) struct NotePage(pub usize);
@@ -10,10 +10,16 @@ use fw_anim::{AnimationBundle, AnimationClip, AnimationClips, CustomAnimationTri
 use fw_button::components::{ButtonBackground, ButtonBundle, ButtonHotspot};
 use fw_ftxm::{FtxmSource, MainMusicTable};
 use mod_item::ItemRegistry;
-use mod_level::{CurrentLevel, Reward};
+use mod_level::{note::NoteRegistry, CurrentLevel, Reward};
 use mod_plant::{components::PlantSeedBundle, metadata::PlantRegistry};
 
-use crate::tag::{BackToTitleButtonTag, ConformButtonTag, MaterialColorAnim, SceneTag};
+use crate::{
+    resource::NotePage,
+    tag::{
+        BackToTitleButtonTag, ConformButtonTag, MaterialColorAnim, NextPageButtonTag,
+        NotePageNumberTag, NoteTextTag, PrevPageButtonTag, SceneTag,
+    },
+};
 
 pub(crate) fn setup_bgm(mut commands: Commands) {
     commands.spawn((
@@ -57,6 +63,7 @@ pub(crate) fn setup_text(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     item_registry: Res<ItemRegistry>,
+    note_registry: Res<NoteRegistry>,
     current_level: Res<CurrentLevel>,
 ) {
     let text = match &current_level.reward {
@@ -67,7 +74,12 @@ pub(crate) fn setup_text(
             };
             item_info.title.clone()
         }
-        Some(Reward::Note { note_path: _ }) => return,
+        Some(Reward::Note { note_path }) => {
+            let Some(note) = note_registry.get(note_path) else {
+                return;
+            };
+            note.title.clone()
+        }
         None => return,
     };
 
@@ -226,6 +238,151 @@ pub(crate) fn setup_reward_info(
     ));
 }
 
+fn spawn_page_button(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    font: &Handle<Font>,
+    text: &str,
+    translation: Vec3,
+    tag: impl Component,
+) {
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -56.0, y: -13.0 },
+                    max: Vec2 { x: 56.0, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            tag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font: font.clone(),
+                        color: Color::BLACK,
+                        font_size: 14.0,
+                    },
+                ),
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+// 信件奖励，显示在信纸上，内容较多时分页
+pub(crate) fn setup_note(
+    mut commands: Commands,
+    current_level: Res<CurrentLevel>,
+    note_registry: Res<NoteRegistry>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut note_page: ResMut<NotePage>,
+) {
+    let Some(Reward::Note { note_path }) = &current_level.reward else {
+        return;
+    };
+    let Some(note) = note_registry.get(note_path) else {
+        return;
+    };
+
+    // 每次都从第一页开始，同时触发页面的刷新
+    note_page.0 = 0;
+
+    // 信纸
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Rectangle::new(440.0, 340.0)).into(),
+            material: materials.add(Color::srgb(0.96, 0.92, 0.78)),
+            transform: Transform::from_xyz(0.0, 30.0, 1.0),
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                String::new(),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    color: Color::srgb(0.3, 0.2, 0.1),
+                },
+            ),
+            text_anchor: Anchor::TopLeft,
+            text_2d_bounds: Text2dBounds {
+                size: Vec2 { x: 400., y: 270. },
+            },
+            transform: Transform::from_xyz(-200.0, 180., 1.1),
+            ..Default::default()
+        },
+        NoteTextTag,
+        SceneTag,
+    ));
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                String::new(),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 16.0,
+                    color: Color::srgb(0.3, 0.2, 0.1),
+                },
+            ),
+            transform: Transform::from_xyz(0.0, -120., 1.1),
+            ..Default::default()
+        },
+        NotePageNumberTag,
+        SceneTag,
+    ));
+
+    // 只有一页时不需要翻页
+    if note.pages.len() <= 1 {
+        return;
+    }
+
+    spawn_page_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "上一页",
+        Vec3::new(-160.0, -170.0, 10.0),
+        PrevPageButtonTag,
+    );
+    spawn_page_button(
+        &mut commands,
+        &asset_server,
+        &font,
+        "下一页",
+        Vec3::new(160.0, -170.0, 10.0),
+        NextPageButtonTag,
+    );
+}
+
 pub(crate) fn setup_conform_button(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font = asset_server.load("font/fzcgbk.ttf");
 
@@ -11,3 +11,16 @@ pub(crate) struct ConformButtonTag;
 
 #[derive(Debug, Component)]
 pub(crate) struct BackToTitleButtonTag;
+
+// 信件当前页的内容
+#[derive(Debug, Component)]
+pub(crate) struct NoteTextTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct NotePageNumberTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct PrevPageButtonTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct NextPageButtonTag;
@@ -3,10 +3,17 @@ use std::any::TypeId;
 use bevy::prelude::*;
 use fw_anim::CustomAnimationTrigger;
 use fw_button::components::ButtonInteraction;
-use mod_level::{CurrentLevel, LevelRegistry, LevelType};
+use mod_level::{note::NoteRegistry, CurrentLevel, LevelRegistry, LevelType, Reward};
+use mod_userdata::UserData;
 use scene_base::GameScene;
 
-use crate::tag::{BackToTitleButtonTag, ConformButtonTag, MaterialColorAnim};
+use crate::{
+    resource::NotePage,
+    tag::{
+        BackToTitleButtonTag, ConformButtonTag, MaterialColorAnim, NextPageButtonTag,
+        NotePageNumberTag, NoteTextTag, PrevPageButtonTag,
+    },
+};
 
 pub(crate) fn update_material_alpha(
     mut targets: Query<
@@ -32,6 +39,7 @@ pub(crate) fn click_conform_button(
     mut next_screen: ResMut<NextState<GameScene>>,
     mut current_level: ResMut<CurrentLevel>,
     level_registry: Res<LevelRegistry>,
+    userdata: Res<UserData>,
 ) {
     let Some(interaction) = button.iter().next() else {
         return;
@@ -40,14 +48,25 @@ pub(crate) fn click_conform_button(
         return;
     }
 
+    // 非冒险模式没有下一关，返回标题画面
     let LevelType::Adventure { level } = current_level.id else {
+        next_screen.set(GameScene::Title);
         return;
     };
 
-    current_level.0 = level_registry
-        .get(&LevelType::Adventure { level: level + 1 })
-        .unwrap()
-        .clone();
+    // 重玩已通关的关卡时回到选关画面，首次通关时进度已经加一
+    if level + 1 != userdata.adventure_progress {
+        next_screen.set(GameScene::LevelSelect);
+        return;
+    }
+
+    // 没有下一关时回到选关画面
+    let Some(next_level) = level_registry.get(&LevelType::Adventure { level: level + 1 }) else {
+        next_screen.set(GameScene::LevelSelect);
+        return;
+    };
+
+    current_level.0 = next_level.clone();
     next_screen.set(GameScene::Game);
 }
 
@@ -63,3 +82,61 @@ pub(crate) fn click_back_to_title_button(
     }
     next_screen.set(GameScene::Title);
 }
+
+pub(crate) fn click_prev_page_button(
+    button: Query<&ButtonInteraction, With<PrevPageButtonTag>>,
+    mut note_page: ResMut<NotePage>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+    note_page.0 = note_page.0.saturating_sub(1);
+}
+
+pub(crate) fn click_next_page_button(
+    button: Query<&ButtonInteraction, With<NextPageButtonTag>>,
+    mut note_page: ResMut<NotePage>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+    // 超过最后一页时由 update_note_page 修正
+    note_page.0 += 1;
+}
+
+// 刷新信件当前页的内容与页码
+pub(crate) fn update_note_page(
+    current_level: Res<CurrentLevel>,
+    note_registry: Res<NoteRegistry>,
+    mut note_page: ResMut<NotePage>,
+    mut note_text: Query<&mut Text, (With<NoteTextTag>, Without<NotePageNumberTag>)>,
+    mut page_number: Query<&mut Text, (With<NotePageNumberTag>, Without<NoteTextTag>)>,
+) {
+    let Some(Reward::Note { note_path }) = &current_level.reward else {
+        return;
+    };
+    let Some(note) = note_registry.get(note_path) else {
+        return;
+    };
+    if note.pages.is_empty() {
+        return;
+    }
+
+    let page = note_page.0.min(note.pages.len() - 1);
+    if page != note_page.0 {
+        note_page.0 = page;
+    }
+
+    for mut text in &mut note_text {
+        text.sections[0].value = note.pages[page].clone();
+    }
+    for mut text in &mut page_number {
+        text.sections[0].value = format!("{} / {}", page + 1, note.pages.len());
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use mod_userdata::UserData;
+use scene_base::GameScene;
+
+mod setup;
+mod tag;
+mod update;
+
+pub struct SceneShopPlugin;
+
+impl Plugin for SceneShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameScene::Shop),
+            (
+                setup::setup_bgm,
+                setup::setup_camera,
+                setup::setup_background,
+                setup::setup_text,
+                setup::setup_items,
+                setup::setup_back_button,
+            ),
+        )
+        .add_systems(OnExit(GameScene::Shop), setup::clear_scene)
+        .add_systems(
+            Update,
+            (
+                update::hover_item,
+                update::click_item,
+                update::click_back_button,
+                (update::update_coin_text, update::update_price_text)
+                    .run_if(resource_changed::<UserData>),
+            )
+                .run_if(in_state(GameScene::Shop)),
+        );
+    }
+}
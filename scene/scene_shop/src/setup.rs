@@ -0,0 +1,257 @@
+use bevy::{prelude::*, render::camera::ScalingMode, sprite::Anchor, text::Text2dBounds};
+use fw_button::components::{ButtonBackground, ButtonBundle, ButtonHotspot};
+use fw_ftxm::{FtxmSource, MainMusicTable};
+use mod_item::ItemRegistry;
+use mod_plant::{components::PlantSeedBundle, metadata::PlantRegistry};
+use mod_userdata::UserData;
+
+use crate::{
+    tag::{BackButtonTag, CoinTextTag, DescriptionTextTag, PriceTextTag, SceneTag, ShopItemTag},
+    update::{coin_text, price_text},
+};
+
+// 物品的排列
+const ITEM_COLUMNS: usize = 4;
+const ITEM_ORIGIN: Vec2 = Vec2 {
+    x: -255.0,
+    y: 110.0,
+};
+const ITEM_SPACING: Vec2 = Vec2 { x: 170.0, y: 170.0 };
+
+pub(crate) fn setup_bgm(mut commands: Commands) {
+    commands.spawn((
+        FtxmSource {
+            pot: MainMusicTable::ZenGarden.into(),
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                far: 1000.,
+                near: -1000.,
+                scaling_mode: ScalingMode::Fixed {
+                    width: 800.,
+                    height: 600.,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_background(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let image = asset_server.load("images/AwardScreen_Back.jpg");
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+}
+
+pub(crate) fn setup_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    userdata: Res<UserData>,
+) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "疯狂戴夫的商店",
+                TextStyle {
+                    font: font.clone(),
+                    color: Color::srgb(0.8, 0.5, 0.2),
+                    ..Default::default()
+                },
+            ),
+            transform: Transform::from_xyz(0.0, 250., 1.0),
+            ..Default::default()
+        },
+        SceneTag,
+    ));
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                coin_text(userdata.coins),
+                TextStyle {
+                    font: font.clone(),
+                    color: Color::srgb(0.83, 0.67, 0.07),
+                    ..Default::default()
+                },
+            ),
+            transform: Transform::from_xyz(0.0, 210., 1.0),
+            ..Default::default()
+        },
+        CoinTextTag,
+        SceneTag,
+    ));
+
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                String::new(),
+                TextStyle {
+                    font,
+                    font_size: 18.0,
+                    color: Color::srgb(0.2, 0.5, 0.8),
+                },
+            ),
+            text_anchor: Anchor::TopCenter,
+            text_2d_bounds: Text2dBounds {
+                size: Vec2 { x: 600.0, y: 60.0 },
+            },
+            transform: Transform::from_xyz(0.0, -200., 1.0),
+            ..Default::default()
+        },
+        DescriptionTextTag,
+        SceneTag,
+    ));
+}
+
+// 有价格的物品才会出售，按价格排列
+pub(crate) fn setup_items(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    item_registry: Res<ItemRegistry>,
+    plant_registry: Res<PlantRegistry>,
+    userdata: Res<UserData>,
+) {
+    let mut items: Vec<_> = item_registry
+        .values()
+        .filter(|item| item.price.is_some())
+        // 解锁的植物不存在时不出售
+        .filter(|item| {
+            item.plant
+                .map_or(true, |plant| plant_registry.contains_key(&plant))
+        })
+        .collect();
+    items.sort_by_key(|item| (item.price, item.name.clone()));
+
+    let font = asset_server.load("font/fzcgbk.ttf");
+    for (i, item) in items.into_iter().enumerate() {
+        let translation = Vec3 {
+            x: ITEM_ORIGIN.x + ITEM_SPACING.x * (i % ITEM_COLUMNS) as f32,
+            y: ITEM_ORIGIN.y - ITEM_SPACING.y * (i / ITEM_COLUMNS) as f32,
+            z: 1.0,
+        };
+
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation(translation)),
+                ShopItemTag { item: item.id },
+                SceneTag,
+            ))
+            .with_children(|parent| {
+                // 植物显示为种子包，其余物品显示图片
+                match item.plant.and_then(|plant| plant_registry.get(&plant)) {
+                    Some(plant_info) => {
+                        let mut plant_bundle = PlantSeedBundle::new(plant_info.clone());
+                        plant_bundle.transform =
+                            Transform::from_xyz(0.0, 20.0, 0.0).with_scale(Vec3::ONE * 1.2);
+                        parent.spawn(plant_bundle);
+                    }
+                    None => {
+                        parent.spawn(SpriteBundle {
+                            texture: asset_server.load(item.texture.clone()),
+                            transform: Transform::from_xyz(0.0, 20.0, 0.0),
+                            ..Default::default()
+                        });
+                    }
+                }
+
+                parent.spawn(Text2dBundle {
+                    text: Text::from_section(
+                        item.name.clone(),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 18.0,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, -45.0, 0.1),
+                    ..Default::default()
+                });
+
+                parent.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            price_text(item, &userdata),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 18.0,
+                                color: Color::srgb(0.83, 0.67, 0.07),
+                            },
+                        ),
+                        transform: Transform::from_xyz(0.0, -70.0, 0.1),
+                        ..Default::default()
+                    },
+                    PriceTextTag { item: item.id },
+                ));
+            });
+    }
+}
+
+pub(crate) fn setup_back_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("font/fzcgbk.ttf");
+
+    commands
+        .spawn((
+            ButtonBundle {
+                background: ButtonBackground {
+                    normal: asset_server.load("images/SeedChooser_Button2.png"),
+                    hover: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    pressed: asset_server.load("images/SeedChooser_Button2_Glow.png"),
+                    disabled: asset_server.load("images/SeedChooser_Button2.png"),
+                },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Center,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(330.0, 270., 10.0),
+                    ..Default::default()
+                },
+                hotspot: ButtonHotspot::Rects(vec![Rect {
+                    min: Vec2 { x: -56.0, y: -13.0 },
+                    max: Vec2 { x: 56.0, y: 13.0 },
+                }]),
+                ..Default::default()
+            },
+            BackButtonTag,
+            SceneTag,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: "主菜单".to_string(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            color: Color::BLACK,
+                            font_size: 14.0,
+                        },
+                    }],
+                    ..Default::default()
+                },
+                text_anchor: Anchor::Center,
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..Default::default()
+            });
+        });
+}
+
+pub(crate) fn clear_scene(mut commands: Commands, targets: Query<Entity, With<SceneTag>>) {
+    for entity in &targets {
+        commands.entity(entity).despawn_recursive();
+    }
+}
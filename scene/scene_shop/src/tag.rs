@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use mod_item::ItemType;
+
+#[derive(Debug, Component)]
+pub(crate) struct SceneTag;
+
+// 出售的物品
+#[derive(Debug, Component)]
+pub(crate) struct ShopItemTag {
+    pub item: ItemType,
+}
+
+// 物品的价格文字，已购买时显示为已购买
+#[derive(Debug, Component)]
+pub(crate) struct PriceTextTag {
+    pub item: ItemType,
+}
+
+#[derive(Debug, Component)]
+pub(crate) struct CoinTextTag;
+
+// 鼠标指向的物品的介绍
+#[derive(Debug, Component)]
+pub(crate) struct DescriptionTextTag;
+
+#[derive(Debug, Component)]
+pub(crate) struct BackButtonTag;
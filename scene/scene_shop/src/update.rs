@@ -0,0 +1,136 @@
+use bevy::{audio::PlaybackMode, prelude::*};
+use fw_button::components::ButtonInteraction;
+use fw_cursor::CursorPosition;
+use mod_item::{Item, ItemRegistry};
+use mod_userdata::UserData;
+use scene_base::GameScene;
+
+use crate::tag::{
+    BackButtonTag, CoinTextTag, DescriptionTextTag, PriceTextTag, SceneTag, ShopItemTag,
+};
+
+// 物品的一半尺寸，用于鼠标判定
+const ITEM_HALF_SIZE: Vec2 = Vec2 { x: 70.0, y: 80.0 };
+
+pub(crate) fn coin_text(coins: u32) -> String {
+    format!("金币：${coins}")
+}
+
+pub(crate) fn price_text(item: &Item, userdata: &UserData) -> String {
+    match (userdata.owns_item(item), item.price) {
+        (false, Some(price)) => format!("${price}"),
+        _ => "已购买".to_owned(),
+    }
+}
+
+fn spawn_se(commands: &mut Commands, asset_server: &AssetServer, path: &'static str) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load(path),
+            settings: PlaybackSettings {
+                mode: PlaybackMode::Despawn,
+                ..Default::default()
+            },
+        },
+        SceneTag,
+    ));
+}
+
+fn hovered_item<'a>(
+    items: &'a Query<(&ShopItemTag, &GlobalTransform)>,
+    cursor_position: &CursorPosition,
+) -> Option<&'a ShopItemTag> {
+    items
+        .iter()
+        .find(|(_, transform)| {
+            let offset = cursor_position.world_position - transform.translation().truncate();
+            offset.x.abs() <= ITEM_HALF_SIZE.x && offset.y.abs() <= ITEM_HALF_SIZE.y
+        })
+        .map(|(item, _)| item)
+}
+
+// 显示鼠标指向的物品的介绍
+pub(crate) fn hover_item(
+    items: Query<(&ShopItemTag, &GlobalTransform)>,
+    cursor_position: Res<CursorPosition>,
+    item_registry: Res<ItemRegistry>,
+    mut texts: Query<&mut Text, With<DescriptionTextTag>>,
+) {
+    let description = hovered_item(&items, &cursor_position)
+        .and_then(|ShopItemTag { item }| item_registry.get(item))
+        .map(|item| item.description.as_str())
+        .unwrap_or_default();
+
+    for mut text in &mut texts {
+        if text.sections[0].value != description {
+            text.sections[0].value = description.to_owned();
+        }
+    }
+}
+
+// 点击购买物品，金币不足或已经拥有时无法购买
+pub(crate) fn click_item(
+    mut commands: Commands,
+    items: Query<(&ShopItemTag, &GlobalTransform)>,
+    cursor_position: Res<CursorPosition>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    item_registry: Res<ItemRegistry>,
+    mut userdata: ResMut<UserData>,
+    asset_server: Res<AssetServer>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(item) = hovered_item(&items, &cursor_position)
+        .and_then(|ShopItemTag { item }| item_registry.get(item))
+    else {
+        return;
+    };
+    let Some(price) = item.price else {
+        return;
+    };
+
+    if userdata.owns_item(item) || userdata.coins < price {
+        spawn_se(&mut commands, &asset_server, "sounds/buzzer.ogg");
+        return;
+    }
+
+    userdata.coins -= price;
+    userdata.obtain_item(item);
+    spawn_se(&mut commands, &asset_server, "sounds/points.ogg");
+}
+
+pub(crate) fn click_back_button(
+    button: Query<&ButtonInteraction, With<BackButtonTag>>,
+    mut next_screen: ResMut<NextState<GameScene>>,
+) {
+    let Some(interaction) = button.iter().next() else {
+        return;
+    };
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+    next_screen.set(GameScene::Title);
+}
+
+pub(crate) fn update_coin_text(
+    userdata: Res<UserData>,
+    mut texts: Query<&mut Text, With<CoinTextTag>>,
+) {
+    for mut text in &mut texts {
+        text.sections[0].value = coin_text(userdata.coins);
+    }
+}
+
+pub(crate) fn update_price_text(
+    userdata: Res<UserData>,
+    item_registry: Res<ItemRegistry>,
+    mut texts: Query<(&mut Text, &PriceTextTag)>,
+) {
+    for (mut text, PriceTextTag { item }) in &mut texts {
+        let Some(item) = item_registry.get(item) else {
+            continue;
+        };
+        text.sections[0].value = price_text(item, &userdata);
+    }
+}
@@ -18,6 +18,8 @@ impl Plugin for SceneTitlePlugin {
                 setup::spawn_bg_center,
                 setup::spawn_bg_left,
                 setup::spawn_bg_right,
+                setup::spawn_almanac_button,
+                setup::spawn_shop_button,
             ),
         )
         .add_systems(OnExit(GameScene::Title), setup::clear_scene)
@@ -26,6 +28,10 @@ impl Plugin for SceneTitlePlugin {
             (
                 update::button_hover_text,
                 update::click_adventure_button,
+                update::click_survival_button,
+                update::click_minigame_button,
+                update::click_almanac_button,
+                update::click_shop_button,
                 update::click_exit_button,
             )
                 .run_if(in_state(GameScene::Title)),
@@ -7,8 +7,8 @@ use fw_ftxm::{FtxmSource, MainMusicTable};
 use mod_userdata::UserData;
 
 use crate::tag::{
-    AdventureButtonTag, ButtonTextTag, ExitButtonTag, HelpButtonTag, MinigameButtonTag,
-    OptionButtonTag, PuzzleButtonTag, SceneTag, SurvialButtonTag,
+    AdventureButtonTag, AlmanacButtonTag, ButtonTextTag, ExitButtonTag, HelpButtonTag,
+    MinigameButtonTag, OptionButtonTag, PuzzleButtonTag, SceneTag, ShopButtonTag, SurvialButtonTag,
 };
 
 pub(crate) fn start_bgm(mut commands: Commands, target: Query<Entity, With<FtxmSource>>) {
@@ -244,7 +244,7 @@ pub(crate) fn spawn_bg_right(
             parent.spawn(Text2dBundle {
                 text: Text {
                     sections: vec![TextSection {
-                        value: (userdata.adventure_progress / 10 + 1).to_string(),
+                        value: ((userdata.adventure_progress - 1) / 10 + 1).to_string(),
                         style: TextStyle {
                             font_size: 18.0,
                             ..Default::default()
@@ -490,6 +490,76 @@ pub(crate) fn spawn_bg_right(
     });
 }
 
+// 图鉴，获得图鉴后才会出现
+pub(crate) fn spawn_almanac_button(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    userdata: Res<UserData>,
+) {
+    if !userdata.unlock_almanac {
+        return;
+    }
+
+    let image = asset_server.load("images/Almanac.png");
+
+    commands.spawn((
+        ButtonBundle {
+            hotspot: ButtonHotspot::Rects(vec![Rect {
+                min: Vec2 { x: -30.0, y: -30.0 },
+                max: Vec2 { x: 30.0, y: 30.0 },
+            }]),
+            background: ButtonBackground {
+                normal: image.clone(),
+                hover: image.clone(),
+                pressed: image.clone(),
+                disabled: image,
+            },
+            sprite: SpriteBundle {
+                transform: Transform::from_xyz(-60.0, -230.0, 5.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        AlmanacButtonTag,
+        SceneTag,
+    ));
+}
+
+// 商店，获得疯狂戴夫的车钥匙后才会出现
+pub(crate) fn spawn_shop_button(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    userdata: Res<UserData>,
+) {
+    if !userdata.unlock_shop {
+        return;
+    }
+
+    let image = asset_server.load("images/CarKeys.png");
+
+    commands.spawn((
+        ButtonBundle {
+            hotspot: ButtonHotspot::Rects(vec![Rect {
+                min: Vec2 { x: -30.0, y: -30.0 },
+                max: Vec2 { x: 30.0, y: 30.0 },
+            }]),
+            background: ButtonBackground {
+                normal: image.clone(),
+                hover: image.clone(),
+                pressed: image.clone(),
+                disabled: image,
+            },
+            sprite: SpriteBundle {
+                transform: Transform::from_xyz(40.0, -230.0, 5.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ShopButtonTag,
+        SceneTag,
+    ));
+}
+
 #[allow(clippy::too_many_arguments)]
 fn spawn_image(
     commands: &mut Commands,
@@ -26,3 +26,9 @@ pub(crate) struct HelpButtonTag;
 
 #[derive(Component)]
 pub(crate) struct ExitButtonTag;
+
+#[derive(Component)]
+pub(crate) struct AlmanacButtonTag;
+
+#[derive(Component)]
+pub(crate) struct ShopButtonTag;
@@ -4,7 +4,10 @@ use mod_level::{CurrentLevel, LevelRegistry, LevelType};
 use mod_userdata::UserData;
 use scene_base::GameScene;
 
-use crate::tag::{AdventureButtonTag, ButtonTextTag, ExitButtonTag};
+use crate::tag::{
+    AdventureButtonTag, AlmanacButtonTag, ButtonTextTag, ExitButtonTag, MinigameButtonTag,
+    ShopButtonTag, SurvialButtonTag,
+};
 
 pub(crate) fn button_hover_text(
     button: Query<&ButtonInteraction>,
@@ -41,6 +44,12 @@ pub(crate) fn click_adventure_button(
 
     *enabled = ButtonEnabled::Disabled;
 
+    // 已经通关过关卡时进入选关画面，否则直接开始第一关
+    if userdata.pass_adventure_count > 0 || userdata.adventure_progress > 1 {
+        next_state.set(GameScene::LevelSelect);
+        return;
+    }
+
     current_level.0 = level_registry
         .get(&LevelType::Adventure {
             level: userdata.adventure_progress,
@@ -51,6 +60,76 @@ pub(crate) fn click_adventure_button(
     next_state.set(GameScene::Game);
 }
 
+// 生存模式，在选关画面中选择普通或无尽模式
+pub(crate) fn click_survival_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<SurvialButtonTag>>,
+    mut next_state: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+
+    next_state.set(GameScene::LevelSelect);
+}
+
+// 小游戏目前只有对战模式，在大厅中选择本地或联机对战
+pub(crate) fn click_minigame_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<MinigameButtonTag>>,
+    mut next_state: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+
+    next_state.set(GameScene::Lobby);
+}
+
+pub(crate) fn click_almanac_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<AlmanacButtonTag>>,
+    mut next_state: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+
+    next_state.set(GameScene::Almanac);
+}
+
+pub(crate) fn click_shop_button(
+    mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<ShopButtonTag>>,
+    mut next_state: ResMut<NextState<GameScene>>,
+) {
+    let Some((mut enabled, interaction)) = button.iter_mut().next() else {
+        return;
+    };
+
+    if !matches!(interaction, ButtonInteraction::Click) {
+        return;
+    }
+
+    *enabled = ButtonEnabled::Disabled;
+
+    next_state.set(GameScene::Shop);
+}
+
 pub(crate) fn click_exit_button(
     mut button: Query<(&mut ButtonEnabled, &ButtonInteraction), With<ExitButtonTag>>,
     mut app_exit_event: EventWriter<AppExit>,